@@ -1,30 +1,44 @@
+use std::collections::BTreeMap;
+
 use cosmwasm_std::{
-    entry_point, from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply,
-    Response, Storage, SubMsg, SubMsgResult,
+    entry_point, from_binary, to_binary, Addr, Attribute, Binary, Deps, DepsMut, Env, MessageInfo,
+    Reply, Response, StdResult, Storage, SubMsg, SubMsgResult,
 };
 
 use secret_toolkit::permit::{validate, Permit, RevokedPermits};
-use secret_toolkit::utils::{pad_handle_result, pad_query_result, InitCallback};
+use secret_toolkit::utils::{pad_handle_result, pad_query_result, HandleCallback, InitCallback};
 
 use secret_toolkit::storage::Keyset;
 use secret_toolkit::viewing_key::{ViewingKey, ViewingKeyStore};
 
 use crate::error::ContractError;
-use crate::state::{BLOCK_SIZE, OFFSPRING_INSTANTIATE_REPLY_ID, PREFIX_REVOKED_PERMITS};
+use crate::state::{
+    BLOCK_SIZE, MAX_CATEGORY_LEN, MAX_COUNT_BATCH, MAX_DESCRIPTION_LEN, MAX_KEY_BATCH,
+    MAX_LABEL_LEN, MAX_NAME_LEN, MAX_OWNER_BATCH, MAX_TITLE_LEN, MAX_TOP_LIMIT, MIN_ENTROPY_LENGTH,
+    OFFSPRING_INSTANTIATE_REPLY_ID, PREFIX_REVOKED_PERMITS,
+};
 use crate::structs::ReplyOffspringInfo;
 use crate::{
     msg::{
-        ExecuteMsg, FilterTypes, HandleAnswer, InstantiateMsg, QueryAnswer, QueryMsg,
+        ExecuteMsg, FilterTypes, HandleAnswer, InstantiateMsg, Page, QueryAnswer, QueryMsg,
         ResponseStatus,
     },
     state::{
-        ACTIVE_STORE, ADMIN, DEFAULT_PAGE_SIZE, INACTIVE_STORE, IS_STOPPED, OFFSPRING_CODE,
-        OFFSPRING_STORAGE, OWNERS_ACTIVE, OWNERS_INACTIVE,
+        ACTIVE_STORE, ADMIN, ALLOW_NEGATIVE_COUNT, CALLBACKS_FROZEN, CATEGORIES, COUNT_BOUNDS,
+        DEFAULT_PAGE_SIZE, FAILED_REGISTRATIONS, INACTIVE_STORE, IS_STOPPED, LAST_MODIFIED_HEIGHT,
+        MAX_ADMIN_PAGE_SIZE, MAX_FAILED_REGISTRATIONS, MAX_OFFSPRING, MAX_PAGE_SIZE, NAME,
+        OFFSPRING_CODE, OFFSPRING_CODES, OFFSPRING_STORAGE, OPERATORS, OWNERLESS, OWNERS_ACTIVE,
+        OWNERS_INACTIVE, OWNERS_SET, PENDING_ACTIVATION, PENDING_CATEGORY, PENDING_CODE_ID,
+        PENDING_KEY_ENTROPY, PUBLIC_INCREMENTS_ALLOWED, REQUIRE_ACTIVATION, RESPONSE_BLOCK_SIZE,
+        SCHEMA_VERSION, SELF_CODE_HASH, TOTAL_CREATED,
+    },
+    structs::{
+        Capabilities, CodeInfo, ContractInfo, CountBounds, OffspringCount, OperatorPermissions,
+        OwnerOffspring, Parity, PublicOffspringInfo, StoreOffspringInfo,
     },
-    structs::{CodeInfo, ContractInfo, StoreOffspringInfo},
 };
 
-use crate::offspring_msg::OffspringInstantiateMsg;
+use crate::offspring_msg::{OffspringExecuteMsg, OffspringInstantiateMsg};
 
 ////////////////////////////////////// Init ///////////////////////////////////////
 /// Returns Result<Response, ContractError>
@@ -34,19 +48,46 @@ use crate::offspring_msg::OffspringInstantiateMsg;
 /// # Arguments
 ///
 /// * `deps`  - DepsMut containing all the contract's external dependencies
-/// * `_env`  - Env of contract's environment
+/// * `env`   - Env of contract's environment
 /// * `info`  - Carries the info of who sent the message and how much native funds were sent
 /// * `msg`   - InitMsg passed in with the instantiation message
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    validate_code_info(&msg.offspring_code_info)?;
     ADMIN.save(deps.storage, &info.sender)?;
+    SELF_CODE_HASH.save(deps.storage, &env.contract.code_hash)?;
     IS_STOPPED.save(deps.storage, &false)?;
     OFFSPRING_CODE.save(deps.storage, &msg.offspring_code_info)?;
+    OFFSPRING_CODES.insert(
+        deps.storage,
+        &msg.offspring_code_info.code_id,
+        &msg.offspring_code_info,
+    )?;
+    let count_bounds = CountBounds {
+        min: msg.min_initial_count,
+        max: msg.max_initial_count,
+    };
+    validate_count_bounds(&count_bounds)?;
+    COUNT_BOUNDS.save(deps.storage, &count_bounds)?;
+    let block_size = msg.block_size.unwrap_or(BLOCK_SIZE);
+    validate_block_size(block_size)?;
+    RESPONSE_BLOCK_SIZE.save(deps.storage, &block_size)?;
+    validate_name(&msg.name)?;
+    NAME.save(deps.storage, &msg.name)?;
+    TOTAL_CREATED.save(deps.storage, &0)?;
+    MAX_OFFSPRING.save(deps.storage, &msg.max_offspring)?;
+    MAX_FAILED_REGISTRATIONS.save(deps.storage, &msg.max_failed_registrations)?;
+    FAILED_REGISTRATIONS.save(deps.storage, &0)?;
+    ALLOW_NEGATIVE_COUNT.save(deps.storage, &msg.allow_negative_count.unwrap_or(true))?;
+    REQUIRE_ACTIVATION.save(deps.storage, &msg.require_activation)?;
+    LAST_MODIFIED_HEIGHT.save(deps.storage, &env.block.height)?;
+    CALLBACKS_FROZEN.save(deps.storage, &false)?;
+    PUBLIC_INCREMENTS_ALLOWED.save(deps.storage, &true)?;
 
     Ok(Response::new())
 }
@@ -67,23 +108,101 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    let block_size = load_block_size(deps.storage)?;
+    // a failed execution reverts this along with the rest of its state changes
+    LAST_MODIFIED_HEIGHT.save(deps.storage, &env.block.height)?;
     let response = match msg {
         ExecuteMsg::CreateOffspring {
             label,
+            title,
+            owner,
+            count,
+            description,
+            owner_only_increment,
+            allowed_parity,
+            local_permit_validation,
+            respect_factory_policy,
+            correlation_id,
+            auto_create_key,
+            code_id,
+            category,
+            report_count,
+        } => try_create_offspring(
+            deps,
+            env,
+            info,
+            label,
+            title,
             owner,
             count,
             description,
-        } => try_create_offspring(deps, env, label, owner, count, description),
-        ExecuteMsg::DeactivateOffspring { owner } => try_deactivate_offspring(deps, info, owner),
-        ExecuteMsg::CreateViewingKey { entropy } => try_create_key(deps, env, info, entropy),
+            owner_only_increment,
+            allowed_parity,
+            local_permit_validation,
+            respect_factory_policy,
+            correlation_id,
+            auto_create_key,
+            code_id,
+            category,
+            report_count,
+        ),
+        ExecuteMsg::DeactivateOffspring { final_count } => {
+            try_deactivate_offspring(deps, env, info, final_count)
+        }
+        ExecuteMsg::ActivateOffspring { offspring } => {
+            try_activate_offspring(deps, env, info, offspring)
+        }
+        ExecuteMsg::OwnerDeactivate { offspring } => {
+            try_owner_set_active(deps, env, info, offspring, false)
+        }
+        ExecuteMsg::OwnerReactivate { offspring } => {
+            try_owner_set_active(deps, env, info, offspring, true)
+        }
+        ExecuteMsg::PurgeOffspring {} => try_purge_offspring(deps, env, info),
+        ExecuteMsg::RenounceOffspring {} => try_renounce_offspring(deps, env, info),
+        ExecuteMsg::TransferOffspring { new_owner } => {
+            try_transfer_offspring(deps, env, info, new_owner)
+        }
+        ExecuteMsg::ReportCount { count } => try_report_count(deps, env, info, count),
+        ExecuteMsg::CreateViewingKey { entropy, padding } => {
+            try_create_key(deps, env, info, entropy, padding)
+        }
         ExecuteMsg::SetViewingKey { key, .. } => try_set_key(deps, info, &key),
+        ExecuteMsg::ManualRegister {
+            offspring,
+            owner,
+            label,
+            code_hash,
+        } => try_manual_register(deps, env, info, offspring, owner, label, code_hash),
         ExecuteMsg::NewOffspringContract {
             offspring_code_info,
         } => try_new_contract(deps, info, offspring_code_info),
+        ExecuteMsg::RemoveOffspringCode { code_id } => {
+            try_remove_offspring_code(deps, info, code_id)
+        }
         ExecuteMsg::SetStatus { stop } => try_set_status(deps, info, stop),
+        ExecuteMsg::SetCountBounds {
+            min_initial_count,
+            max_initial_count,
+        } => try_set_count_bounds(deps, info, min_initial_count, max_initial_count),
+        ExecuteMsg::SetMaxOffspring { max_offspring } => {
+            try_set_max_offspring(deps, info, max_offspring)
+        }
+        ExecuteMsg::ResetFailedRegistrations {} => try_reset_failed_registrations(deps, info),
+        ExecuteMsg::SetBlockSize { block_size } => try_set_block_size(deps, info, block_size),
+        ExecuteMsg::SetPublicIncrements { allowed } => {
+            try_set_public_increments(deps, info, allowed)
+        }
+        ExecuteMsg::SetName { name } => try_set_name(deps, info, name),
+        ExecuteMsg::SetCallbacksFrozen { frozen } => try_set_callbacks_frozen(deps, info, frozen),
+        ExecuteMsg::RebuildOwnerIndex { owner } => try_rebuild_owner_index(deps, info, owner),
+        ExecuteMsg::SetOperator {
+            address,
+            permissions,
+        } => try_set_operator(deps, info, address, permissions),
         ExecuteMsg::RevokePermit { permit_name, .. } => revoke_permit(deps, info, permit_name),
     };
-    pad_handle_result(response, BLOCK_SIZE)
+    pad_handle_result(response, block_size)
 }
 
 /// Returns Result<Response, ContractError>
@@ -92,30 +211,117 @@ pub fn execute(
 ///
 /// # Arguments
 ///
-/// * `deps`        - DepsMut containing all the contract's external dependencies
-/// * `env`         - Env of contract's environment
-/// * `password`    - String containing the password to give the offspring
-/// * `owner`       - address of the owner associated to this offspring contract
-/// * `count`       - the count for the counter template
-/// * `description` - optional free-form text string owner may have used to describe the offspring
+/// * `deps`                    - DepsMut containing all the contract's external dependencies
+/// * `env`                     - Env of contract's environment
+/// * `info`                    - Carries the info of who sent the message and how much native funds were sent along
+/// * `password`                - String containing the password to give the offspring
+/// * `title`                   - optional display name of the offspring
+/// * `owner`                   - address of the owner associated to this offspring contract
+/// * `count`                   - the count for the counter template
+/// * `description`             - optional free-form text string owner may have used to describe the offspring
+/// * `owner_only_increment`    - whether only the owner can increment the counter
+/// * `allowed_parity`          - optional parity every count of the offspring must have
+/// * `local_permit_validation` - whether the offspring validates query permits itself
+/// * `respect_factory_policy`  - whether the offspring follows the public increments policy
+/// * `correlation_id`          - optional id echoed back when the offspring is registered
+/// * `auto_create_key`         - optional entropy used to also create a viewing key for the owner
+/// * `code_id`                 - optional code id of the offspring version to instantiate
+/// * `category`                - optional category to group the offspring by
+/// * `report_count`            - whether the offspring reports its count to the factory
+#[allow(clippy::too_many_arguments)]
 fn try_create_offspring(
     deps: DepsMut,
     env: Env,
+    info: MessageInfo,
     label: String,
+    title: Option<String>,
     owner: String,
     count: i32,
     description: Option<String>,
+    owner_only_increment: bool,
+    allowed_parity: Option<Parity>,
+    local_permit_validation: bool,
+    respect_factory_policy: bool,
+    correlation_id: Option<String>,
+    auto_create_key: Option<String>,
+    code_id: Option<u64>,
+    category: Option<String>,
+    report_count: bool,
 ) -> Result<Response, ContractError> {
     if IS_STOPPED.load(deps.storage)? {
         return Err(ContractError::Stopped {});
     }
+    // the registration would be rejected in the reply anyway
+    enforce_callbacks_not_frozen(deps.storage)?;
+
+    if let Some(max) = MAX_OFFSPRING.load(deps.storage)? {
+        if TOTAL_CREATED.load(deps.storage)? >= max {
+            return Err(ContractError::AtCapacity { max });
+        }
+    }
+
+    if label.len() > MAX_LABEL_LEN {
+        return Err(ContractError::LabelTooLong { max: MAX_LABEL_LEN });
+    }
+    if matches!(&title, Some(title) if title.len() > MAX_TITLE_LEN) {
+        return Err(ContractError::TitleTooLong { max: MAX_TITLE_LEN });
+    }
+    if matches!(&description, Some(desc) if desc.len() > MAX_DESCRIPTION_LEN) {
+        return Err(ContractError::DescriptionTooLong {
+            max: MAX_DESCRIPTION_LEN,
+        });
+    }
+
+    if matches!(&category, Some(category) if category.len() > MAX_CATEGORY_LEN) {
+        return Err(ContractError::CategoryTooLong {
+            max: MAX_CATEGORY_LEN,
+        });
+    }
+
+    if count < 0 && !ALLOW_NEGATIVE_COUNT.load(deps.storage)? {
+        return Err(ContractError::NegativeCount { count });
+    }
+    let count_bounds = COUNT_BOUNDS.load(deps.storage)?;
+    if !count_bounds.contains(count) {
+        return Err(ContractError::CountOutOfBounds {
+            count,
+            min: count_bounds.min,
+            max: count_bounds.max,
+        });
+    }
 
     let owner_addr = deps.api.addr_validate(&owner)?;
 
-    let factory = ContractInfo {
-        code_hash: env.contract.code_hash,
-        address: env.contract.address,
+    let current_code = OFFSPRING_CODE.load(deps.storage)?;
+    let offspring_code = match code_id {
+        Some(code_id) if code_id != current_code.code_id => OFFSPRING_CODES
+            .get(deps.storage, &code_id)
+            .ok_or(ContractError::UnknownCodeId { code_id })?,
+        _ => current_code,
     };
+    // the reply records the offspring with the code id it was instantiated with
+    PENDING_CODE_ID.save(deps.storage, &offspring_code.code_id)?;
+    if let Some(category) = category {
+        PENDING_CATEGORY.save(deps.storage, &category)?;
+    }
+
+    if let Some(entropy) = auto_create_key {
+        // a viewing key is only handed to the address it authenticates
+        if info.sender != owner_addr {
+            return Err(ContractError::Unauthorized {});
+        }
+        if entropy.len() < MIN_ENTROPY_LENGTH {
+            return Err(ContractError::InsufficientEntropy {
+                min: MIN_ENTROPY_LENGTH,
+            });
+        }
+        // the key is created in the reply once the offspring is registered, so that a failed
+        // registration does not replace the owner's current key. The reply also returns the key,
+        // since its data replaces this response's data
+        PENDING_KEY_ENTROPY.save(deps.storage, &entropy)?;
+    }
+
+    let factory = load_self_info(deps.storage, env)?;
 
     let initmsg = OffspringInstantiateMsg {
         factory,
@@ -123,9 +329,17 @@ fn try_create_offspring(
         owner: owner_addr,
         count,
         description,
+        title,
+        owner_only_increment,
+        allowed_parity,
+        local_permit_validation,
+        respect_factory_policy,
+        report_count,
+        // the reply lists the offspring as inactive until its owner activates it
+        start_inactive: REQUIRE_ACTIVATION.load(deps.storage)?,
+        correlation_id,
     };
 
-    let offspring_code = OFFSPRING_CODE.load(deps.storage)?;
     let init_submsg = SubMsg::reply_always(
         initmsg.to_cosmos_msg(
             label,
@@ -152,521 +366,3382 @@ fn try_create_offspring(
 /// Returns Result<Response, ContractError>
 ///
 /// deactivates the offspring by saving its info and adding/removing it to/from the
-/// appropriate lists
+/// appropriate lists. The owner recorded at registration is used to locate the owner's lists, so
+/// an offspring can not corrupt another owner's lists.
 ///
 /// # Arguments
 ///
-/// * `deps`  - DepsMut containing all the contract's external dependencies
-/// * `info`  - Carries the info of who sent the message and how much native funds were sent along
-/// * `owner` - Addr of offspring's owner
+/// * `deps`        - DepsMut containing all the contract's external dependencies
+/// * `env`         - Env of contract's environment
+/// * `info`        - Carries the info of who sent the message and how much native funds were sent along
+/// * `final_count` - optional count of the offspring when it was deactivated
 fn try_deactivate_offspring(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    owner: Addr,
+    final_count: Option<i32>,
 ) -> Result<Response, ContractError> {
+    enforce_callbacks_not_frozen(deps.storage)?;
     let offspring_addr = &info.sender;
+    let mut offspring = OFFSPRING_STORAGE
+        .get(deps.storage, offspring_addr)
+        .ok_or_else(|| ContractError::OffspringNotFound {
+            address: offspring_addr.to_string(),
+        })?;
 
+    // an offspring awaiting activation is already in the inactive lists, it just stops waiting
+    let is_pending = PENDING_ACTIVATION.contains(deps.storage, offspring_addr);
     // verify offspring is in active list
     let is_active = ACTIVE_STORE.contains(deps.storage, offspring_addr);
-    if !is_active {
+    if !is_pending && !is_active {
         return Err(ContractError::CustomError {
             val: "This offspring is already not active".to_string(),
         });
     }
+    // keep the count the offspring ended with, if it reports its count
+    if let Some(final_count) = final_count.filter(|_| offspring.reports_count) {
+        offspring.last_count = final_count;
+        OFFSPRING_STORAGE.insert(deps.storage, offspring_addr, &offspring)?;
+    }
+
+    if is_pending {
+        PENDING_ACTIVATION.remove(deps.storage, offspring_addr)?;
+    } else {
+        move_to_inactive(deps.storage, offspring_addr, &offspring.owner)?;
+    }
+
+    Ok(Response::new().add_attributes(offspring_attributes(
+        "deactivate_offspring",
+        &offspring,
+        &env,
+    )))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// removes an inactive offspring from all of the factory's records. The owner recorded at
+/// registration is used to locate the owner's lists.
+///
+/// # Arguments
+///
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `env`  - Env of contract's environment
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+fn try_purge_offspring(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let offspring_addr = &info.sender;
+    let offspring = OFFSPRING_STORAGE
+        .get(deps.storage, offspring_addr)
+        .ok_or_else(|| ContractError::OffspringNotFound {
+            address: offspring_addr.to_string(),
+        })?;
+
+    // verify offspring is in inactive list
+    if !INACTIVE_STORE.contains(deps.storage, offspring_addr) {
+        return Err(ContractError::CustomError {
+            val: "Only an inactive offspring can be purged".to_string(),
+        });
+    }
+
+    INACTIVE_STORE.remove(deps.storage, offspring_addr)?;
+    if PENDING_ACTIVATION.contains(deps.storage, offspring_addr) {
+        PENDING_ACTIVATION.remove(deps.storage, offspring_addr)?;
+    }
+    OWNERS_INACTIVE
+        .add_suffix(offspring.owner.to_string().as_bytes())
+        .remove(deps.storage, offspring_addr)?;
+    OFFSPRING_STORAGE.remove(deps.storage, offspring_addr)?;
+
+    // forget the owner once they have no offspring left
+    if owner_counts(deps.storage, &offspring.owner)? == (0, 0) {
+        OWNERS_SET.remove(deps.storage, &offspring.owner)?;
+    }
+
+    Ok(Response::new().add_attributes(offspring_attributes("purge_offspring", &offspring, &env)))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// moves an offspring from its owner's lists to the new owner's lists after the offspring changed
+/// owners. This is called by the offspring in the same transaction as the transfer, so if it fails
+/// the transfer is reverted as well.
+///
+/// # Arguments
+///
+/// * `deps`      - DepsMut containing all the contract's external dependencies
+/// * `env`       - Env of contract's environment
+/// * `info`      - Carries the info of who sent the message and how much native funds were sent along
+/// * `new_owner` - the offspring's new owner
+fn try_transfer_offspring(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_owner: Addr,
+) -> Result<Response, ContractError> {
+    enforce_callbacks_not_frozen(deps.storage)?;
+    let offspring_addr = &info.sender;
+    let mut offspring = OFFSPRING_STORAGE
+        .get(deps.storage, offspring_addr)
+        .ok_or(ContractError::Unauthorized {})?;
+    let new_owner = deps.api.addr_validate(new_owner.as_str())?;
+    let old_owner = offspring.owner.clone();
+    if new_owner == old_owner {
+        return Ok(Response::new());
+    }
+
+    let old_active = OWNERS_ACTIVE.add_suffix(old_owner.to_string().as_bytes());
+    let new_active = OWNERS_ACTIVE.add_suffix(new_owner.to_string().as_bytes());
+    if old_active.contains(deps.storage, offspring_addr) {
+        old_active.remove(deps.storage, offspring_addr)?;
+        new_active.insert(deps.storage, offspring_addr)?;
+    }
+    let old_inactive = OWNERS_INACTIVE.add_suffix(old_owner.to_string().as_bytes());
+    let new_inactive = OWNERS_INACTIVE.add_suffix(new_owner.to_string().as_bytes());
+    if old_inactive.contains(deps.storage, offspring_addr) {
+        old_inactive.remove(deps.storage, offspring_addr)?;
+        new_inactive.insert(deps.storage, offspring_addr)?;
+    }
+
+    // forget the old owner once they have no offspring left
+    if owner_counts(deps.storage, &old_owner)? == (0, 0) {
+        OWNERS_SET.remove(deps.storage, &old_owner)?;
+    }
+    OWNERS_SET.insert(deps.storage, &new_owner, &true)?;
+
+    offspring.owner = new_owner;
+    OFFSPRING_STORAGE.insert(deps.storage, offspring_addr, &offspring)?;
+
+    Ok(Response::new()
+        .add_attributes(offspring_attributes("transfer_offspring", &offspring, &env))
+        .add_attribute("previous_owner", old_owner))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// moves an offspring from its owner's lists to the ownerless offspring after its owner renounced
+/// ownership. The offspring's own address is recorded as its owner, since no one can act as it.
+/// This is called by the offspring in the same transaction as the renouncement, so if it fails the
+/// renouncement is reverted as well.
+///
+/// # Arguments
+///
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `env`  - Env of contract's environment
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+fn try_renounce_offspring(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    enforce_callbacks_not_frozen(deps.storage)?;
+    let offspring_addr = &info.sender;
+    let mut offspring = OFFSPRING_STORAGE
+        .get(deps.storage, offspring_addr)
+        .ok_or(ContractError::Unauthorized {})?;
+    if OWNERLESS.contains(deps.storage, offspring_addr) {
+        return Ok(Response::new());
+    }
+    let old_owner = offspring.owner.clone();
+
+    let owners_active = OWNERS_ACTIVE.add_suffix(old_owner.to_string().as_bytes());
+    if owners_active.contains(deps.storage, offspring_addr) {
+        owners_active.remove(deps.storage, offspring_addr)?;
+    }
+    let owners_inactive = OWNERS_INACTIVE.add_suffix(old_owner.to_string().as_bytes());
+    if owners_inactive.contains(deps.storage, offspring_addr) {
+        owners_inactive.remove(deps.storage, offspring_addr)?;
+    }
+    // forget the old owner once they have no offspring left
+    if owner_counts(deps.storage, &old_owner)? == (0, 0) {
+        OWNERS_SET.remove(deps.storage, &old_owner)?;
+    }
+    OWNERLESS.insert(deps.storage, offspring_addr)?;
+
+    offspring.owner = offspring_addr.clone();
+    OFFSPRING_STORAGE.insert(deps.storage, offspring_addr, &offspring)?;
 
+    Ok(Response::new()
+        .add_attributes(offspring_attributes("renounce_offspring", &offspring, &env))
+        .add_attribute("previous_owner", old_owner))
+}
+
+/// Returns Result<(), ContractError>
+///
+/// moves an offspring from the active lists to the inactive lists
+///
+/// # Arguments
+///
+/// * `storage`   - a mutable reference to the contract's storage
+/// * `offspring` - a reference to the address of the offspring
+/// * `owner`     - a reference to the address of the offspring's owner
+fn move_to_inactive(
+    storage: &mut dyn Storage,
+    offspring: &Addr,
+    owner: &Addr,
+) -> Result<(), ContractError> {
     // remove from active
-    ACTIVE_STORE.remove(deps.storage, offspring_addr)?;
+    ACTIVE_STORE.remove(storage, offspring)?;
 
     // save to inactive
-    INACTIVE_STORE.insert(deps.storage, offspring_addr)?;
+    INACTIVE_STORE.insert(storage, offspring)?;
 
     // remove from owner's active
     OWNERS_ACTIVE
         .add_suffix(owner.to_string().as_bytes())
-        .remove(deps.storage, offspring_addr)?;
+        .remove(storage, offspring)?;
 
     // save to owner's inactive
     OWNERS_INACTIVE
         .add_suffix(owner.to_string().as_bytes())
-        .insert(deps.storage, offspring_addr)?;
+        .insert(storage, offspring)?;
 
-    Ok(Response::new())
+    Ok(())
+}
+
+/// Returns Result<(), ContractError>
+///
+/// moves an offspring from the inactive lists to the active lists
+///
+/// # Arguments
+///
+/// * `storage`   - a mutable reference to the contract's storage
+/// * `offspring` - a reference to the address of the offspring
+/// * `owner`     - a reference to the address of the offspring's owner
+fn move_to_active(
+    storage: &mut dyn Storage,
+    offspring: &Addr,
+    owner: &Addr,
+) -> Result<(), ContractError> {
+    // remove from inactive
+    INACTIVE_STORE.remove(storage, offspring)?;
+
+    // save to active
+    ACTIVE_STORE.insert(storage, offspring)?;
+
+    // remove from owner's inactive
+    OWNERS_INACTIVE
+        .add_suffix(owner.to_string().as_bytes())
+        .remove(storage, offspring)?;
+
+    // save to owner's active
+    OWNERS_ACTIVE
+        .add_suffix(owner.to_string().as_bytes())
+        .insert(storage, offspring)?;
+
+    Ok(())
 }
 
 /// Returns Result<Response, ContractError>
 ///
-/// allows admin to edit the offspring contract version.
+/// lets an owner deactivate or reactivate one of their offspring from the factory, and tells the
+/// offspring to freeze or unfreeze accordingly
 ///
 /// # Arguments
 ///
-/// * `deps`                - DepsMut containing all the contract's external dependencies
-/// * `info`                - Carries the info of who sent the message and how much native funds were sent along
-/// * `offspring_code_info` - CodeInfo of the new offspring version
-fn try_new_contract(
+/// * `deps`      - DepsMut containing all the contract's external dependencies
+/// * `env`       - Env of contract's environment
+/// * `info`      - Carries the info of who sent the message and how much native funds were sent along
+/// * `offspring` - address of the offspring
+/// * `active`    - whether the offspring should be active
+fn try_owner_set_active(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    offspring_code_info: CodeInfo,
+    offspring: String,
+    active: bool,
 ) -> Result<Response, ContractError> {
-    // only allow admin to do this
-    let sender = info.sender;
-    if ADMIN.load(deps.storage)? != sender {
+    let offspring_addr = deps.api.addr_validate(&offspring)?;
+    let offspring_info = OFFSPRING_STORAGE
+        .get(deps.storage, &offspring_addr)
+        .ok_or_else(|| ContractError::OffspringNotFound {
+            address: offspring_addr.to_string(),
+        })?;
+    if offspring_info.owner != info.sender {
         return Err(ContractError::Unauthorized {});
     }
-    OFFSPRING_CODE.save(deps.storage, &offspring_code_info)?;
+    // offspring awaiting activation are activated with ActivateOffspring
+    if PENDING_ACTIVATION.contains(deps.storage, &offspring_addr) {
+        return Err(ContractError::CustomError {
+            val: "This offspring is awaiting activation".to_string(),
+        });
+    }
+    if ACTIVE_STORE.contains(deps.storage, &offspring_addr) == active {
+        return Err(ContractError::CustomError {
+            val: format!(
+                "This offspring is already {}",
+                if active { "active" } else { "not active" }
+            ),
+        });
+    }
+
+    let (callback, action) = if active {
+        move_to_active(deps.storage, &offspring_addr, &offspring_info.owner)?;
+        (
+            OffspringExecuteMsg::FactoryUnfreeze {},
+            "reactivate_offspring",
+        )
+    } else {
+        move_to_inactive(deps.storage, &offspring_addr, &offspring_info.owner)?;
+        (
+            OffspringExecuteMsg::FactoryFreeze {},
+            "deactivate_offspring",
+        )
+    };
+    let callback_msg = callback.to_cosmos_msg(
+        offspring_info.contract.code_hash.clone(),
+        offspring_addr.to_string(),
+        None,
+    )?;
 
     let resp_data = to_binary(&HandleAnswer::Status {
         status: ResponseStatus::Success,
         message: None,
     })?;
-    Ok(Response::new().set_data(resp_data))
+    Ok(Response::new()
+        .add_message(callback_msg)
+        .add_attributes(offspring_attributes(action, &offspring_info, &env))
+        .set_data(resp_data))
 }
 
 /// Returns Result<Response, ContractError>
 ///
-/// allows admin to change the factory status to (dis)allow the creation of new offspring
+/// moves an offspring awaiting activation to the active lists and lets the offspring know. Can
+/// only be executed by the offspring's owner.
 ///
 /// # Arguments
 ///
-/// * `deps` - DepsMut containing all the contract's external dependencies
-/// * `info` - Carries the info of who sent the message and how much native funds were sent along
-/// * `stop` - true if the factory should disallow offspring creation
-fn try_set_status(deps: DepsMut, info: MessageInfo, stop: bool) -> Result<Response, ContractError> {
-    // only allow admin to do this
-    let sender = info.sender;
-    if ADMIN.load(deps.storage)? != sender {
+/// * `deps`      - DepsMut containing all the contract's external dependencies
+/// * `env`       - Env of contract's environment
+/// * `info`      - Carries the info of who sent the message and how much native funds were sent along
+/// * `offspring` - address of the offspring to activate
+fn try_activate_offspring(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offspring: String,
+) -> Result<Response, ContractError> {
+    let offspring_addr = deps.api.addr_validate(&offspring)?;
+    let offspring_info = OFFSPRING_STORAGE
+        .get(deps.storage, &offspring_addr)
+        .ok_or_else(|| ContractError::OffspringNotFound {
+            address: offspring_addr.to_string(),
+        })?;
+    if offspring_info.owner != info.sender {
         return Err(ContractError::Unauthorized {});
     }
-    IS_STOPPED.save(deps.storage, &stop)?;
+    if !PENDING_ACTIVATION.contains(deps.storage, &offspring_addr) {
+        return Err(ContractError::CustomError {
+            val: "This offspring is not awaiting activation".to_string(),
+        });
+    }
+    PENDING_ACTIVATION.remove(deps.storage, &offspring_addr)?;
+
+    move_to_active(deps.storage, &offspring_addr, &offspring_info.owner)?;
+    let callback_msg = OffspringExecuteMsg::FactoryUnfreeze {}.to_cosmos_msg(
+        offspring_info.contract.code_hash.clone(),
+        offspring_addr.to_string(),
+        None,
+    )?;
 
     let resp_data = to_binary(&HandleAnswer::Status {
         status: ResponseStatus::Success,
         message: None,
     })?;
-    Ok(Response::new().set_data(resp_data))
+    Ok(Response::new()
+        .add_message(callback_msg)
+        .add_attributes(offspring_attributes(
+            "activate_offspring",
+            &offspring_info,
+            &env,
+        ))
+        .set_data(resp_data))
 }
 
 /// Returns Result<Response, ContractError>
 ///
-/// create a viewing key
+/// caches the count reported by an offspring. Only offspring created with report_count can report
+/// their count
 ///
 /// # Arguments
 ///
-/// * `deps`    - DepsMut containing all the contract's external dependencies
-/// * `env`     - Env of contract's environment
-/// * `info`    - Carries the info of who sent the message and how much native funds were sent along
-/// * `entropy` - string to be used as an entropy source for randomization
-fn try_create_key(
+/// * `deps`  - DepsMut containing all the contract's external dependencies
+/// * `env`   - Env of contract's environment
+/// * `info`  - Carries the info of who sent the message and how much native funds were sent along
+/// * `count` - the offspring's current count
+fn try_report_count(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    entropy: String,
+    count: i32,
 ) -> Result<Response, ContractError> {
-    let key = ViewingKey::create(
-        deps.storage,
-        &info,
-        &env,
-        info.sender.as_str(),
-        entropy.as_bytes(),
-    );
+    let offspring_addr = &info.sender;
+    let mut offspring = OFFSPRING_STORAGE
+        .get(deps.storage, offspring_addr)
+        .ok_or(ContractError::Unauthorized {})?;
+    if !offspring.reports_count {
+        return Err(ContractError::CustomError {
+            val: "This offspring was not created to report its count".to_string(),
+        });
+    }
+    offspring.last_count = count;
+    OFFSPRING_STORAGE.insert(deps.storage, offspring_addr, &offspring)?;
 
-    Ok(Response::new().add_attribute("viewing_key", key))
+    Ok(Response::new().add_attributes(offspring_attributes("report_count", &offspring, &env)))
 }
 
 /// Returns Result<Response, ContractError>
 ///
-/// sets the viewing key
+/// allows admin or an operator with can_set_code to edit the offspring contract version.
 ///
 /// # Arguments
 ///
-/// * `deps` - DepsMut containing all the contract's external dependencies
-/// * `info` - Carries the info of who sent the message and how much native funds were sent along
-/// * `key`  - string slice to be used as the viewing key
-fn try_set_key(deps: DepsMut, info: MessageInfo, key: &str) -> Result<Response, ContractError> {
-    ViewingKey::set(deps.storage, info.sender.as_str(), key);
+/// * `deps`                - DepsMut containing all the contract's external dependencies
+/// * `info`                - Carries the info of who sent the message and how much native funds were sent along
+/// * `offspring_code_info` - CodeInfo of the new offspring version
+fn try_new_contract(
+    deps: DepsMut,
+    info: MessageInfo,
+    offspring_code_info: CodeInfo,
+) -> Result<Response, ContractError> {
+    enforce_admin_or_operator(deps.storage, &info.sender, |p| p.can_set_code)?;
+    validate_code_info(&offspring_code_info)?;
+    OFFSPRING_CODE.save(deps.storage, &offspring_code_info)?;
+    OFFSPRING_CODES.insert(
+        deps.storage,
+        &offspring_code_info.code_id,
+        &offspring_code_info,
+    )?;
 
-    Ok(Response::new().add_attribute("viewing_key", key))
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Success,
+        message: None,
+    })?;
+    Ok(Response::new().set_data(resp_data))
 }
 
 /// Returns Result<Response, ContractError>
 ///
-/// Revokes a all query permits with the given name
+/// allows admin or an operator with can_set_code to remove an offspring contract version. Offspring
+/// already instantiated with it keep their recorded code id.
 ///
 /// # Arguments
 ///
-/// * `deps` - DepsMut containing all the contract's external dependencies
-/// * `info` - Carries the info of who sent the message and how much native funds were sent along
-/// * `key`  - string slice to be used as the viewing key
-fn revoke_permit(
+/// * `deps`    - DepsMut containing all the contract's external dependencies
+/// * `info`    - Carries the info of who sent the message and how much native funds were sent along
+/// * `code_id` - code id of the offspring version to remove
+fn try_remove_offspring_code(
     deps: DepsMut,
     info: MessageInfo,
-    permit_name: String,
+    code_id: u64,
 ) -> Result<Response, ContractError> {
-    RevokedPermits::revoke_permit(
-        deps.storage,
-        PREFIX_REVOKED_PERMITS,
-        info.sender.as_ref(),
-        &permit_name,
-    );
+    enforce_admin_or_operator(deps.storage, &info.sender, |p| p.can_set_code)?;
+    if OFFSPRING_CODE.load(deps.storage)?.code_id == code_id {
+        return Err(ContractError::CustomError {
+            val: "The current offspring version can not be removed".to_string(),
+        });
+    }
+    if !OFFSPRING_CODES.contains(deps.storage, &code_id) {
+        return Err(ContractError::UnknownCodeId { code_id });
+    }
+    OFFSPRING_CODES.remove(deps.storage, &code_id)?;
 
-    Ok(Response::new())
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Success,
+        message: None,
+    })?;
+    Ok(Response::new().set_data(resp_data))
+}
+
+/// Returns Result<(), ContractError>
+///
+/// makes sure that the offspring code info can be used to instantiate offspring, i.e. the code id
+/// is not zero and the code hash is a 64 character hex string
+///
+/// # Arguments
+///
+/// * `code_info` - a reference to the CodeInfo being validated
+fn validate_code_info(code_info: &CodeInfo) -> Result<(), ContractError> {
+    if code_info.code_id == 0 {
+        return Err(ContractError::InvalidCodeInfo {
+            msg: "code_id cannot be 0".to_string(),
+        });
+    }
+    if code_info.code_hash.len() != 64
+        || !code_info.code_hash.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Err(ContractError::InvalidCodeInfo {
+            msg: "code_hash must be a 64 character hex string".to_string(),
+        });
+    }
+    Ok(())
 }
 
-/////////////////////////////////////// Reply /////////////////////////////////////
 /// Returns Result<Response, ContractError>
 ///
+/// allows admin or an operator with can_pause to change the factory status to (dis)allow the
+/// creation of new offspring. The response message tells whether the status actually changed.
+///
 /// # Arguments
 ///
 /// * `deps` - DepsMut containing all the contract's external dependencies
-/// * `msg` - QueryMsg passed in with the query call
-#[entry_point]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
-    match msg.id {
-        OFFSPRING_INSTANTIATE_REPLY_ID => handle_instantiate_reply(deps, msg),
-        id => Err(ContractError::UnexpectedReplyId { id }),
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+/// * `stop` - true if the factory should disallow offspring creation
+fn try_set_status(deps: DepsMut, info: MessageInfo, stop: bool) -> Result<Response, ContractError> {
+    enforce_admin_or_operator(deps.storage, &info.sender, |p| p.can_pause)?;
+    let message = if IS_STOPPED.load(deps.storage)? == stop {
+        "Factory status is unchanged"
+    } else {
+        IS_STOPPED.save(deps.storage, &stop)?;
+        "Factory status is changed"
+    };
+
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Success,
+        message: Some(message.to_string()),
+    })?;
+    Ok(Response::new()
+        .add_attribute("factory_stopped", stop.to_string())
+        .set_data(resp_data))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// allows admin to change the bounds of the initial count of new offspring
+///
+/// # Arguments
+///
+/// * `deps`              - DepsMut containing all the contract's external dependencies
+/// * `info`              - Carries the info of who sent the message and how much native funds were sent along
+/// * `min_initial_count` - optional minimum initial count
+/// * `max_initial_count` - optional maximum initial count
+fn try_set_count_bounds(
+    deps: DepsMut,
+    info: MessageInfo,
+    min_initial_count: Option<i32>,
+    max_initial_count: Option<i32>,
+) -> Result<Response, ContractError> {
+    // only allow admin to do this
+    if ADMIN.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
     }
+    let count_bounds = CountBounds {
+        min: min_initial_count,
+        max: max_initial_count,
+    };
+    validate_count_bounds(&count_bounds)?;
+    COUNT_BOUNDS.save(deps.storage, &count_bounds)?;
+
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Success,
+        message: None,
+    })?;
+    Ok(Response::new().set_data(resp_data))
 }
 
-fn handle_instantiate_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
-    // The parsing process below can be handled easier if one imports cw-plus
-    // See: https://github.com/CosmWasm/cw-plus/blob/main/packages/utils/src/parse_reply.rs
-    match msg.result {
-        SubMsgResult::Ok(s) => match s.data {
-            Some(bin) => {
-                let reply_info: ReplyOffspringInfo = from_binary(&bin)?;
-                register_offspring_impl(deps, reply_info)
-            }
-            None => Err(ContractError::CustomError {
-                val: "Init didn't response with contract address".to_string(),
-            }),
-        },
-        SubMsgResult::Err(e) => Err(ContractError::CustomError { val: e }),
+/// Returns Result<(), ContractError>
+///
+/// makes sure that the minimum initial count is not greater than the maximum initial count
+///
+/// # Arguments
+///
+/// * `count_bounds` - a reference to the CountBounds being validated
+fn validate_count_bounds(count_bounds: &CountBounds) -> Result<(), ContractError> {
+    if let (Some(min), Some(max)) = (count_bounds.min, count_bounds.max) {
+        if min > max {
+            return Err(ContractError::InvalidCountBounds {});
+        }
     }
+    Ok(())
 }
 
 /// Returns Result<Response, ContractError>
 ///
-/// Registers the calling offspring by saving its info and adding it to the appropriate lists
+/// allows admin to change the maximum number of offspring the factory can ever create
 ///
 /// # Arguments
 ///
-/// * `deps`       - DepsMut containing all the contract's external dependencies
-/// * `reply_info` - reference to ReplyOffspringInfo of the offspring that is trying to register
-fn register_offspring_impl(
+/// * `deps`          - DepsMut containing all the contract's external dependencies
+/// * `info`          - Carries the info of who sent the message and how much native funds were sent along
+/// * `max_offspring` - optional new cap. Removes the cap if none
+fn try_set_max_offspring(
     deps: DepsMut,
-    reply_info: ReplyOffspringInfo,
+    info: MessageInfo,
+    max_offspring: Option<u64>,
 ) -> Result<Response, ContractError> {
-    // convert register offspring info to storage format
-    let offspring = reply_info.to_store_offspring_info();
-
-    // save the offspring info
-    OFFSPRING_STORAGE.insert(deps.storage, &reply_info.address, &offspring)?;
-
-    // add active list
-    ACTIVE_STORE.insert(deps.storage, &reply_info.address)?;
-    // add to owner's active list
-    OWNERS_ACTIVE
-        .add_suffix(reply_info.owner.to_string().as_bytes())
-        .insert(deps.storage, &reply_info.address)?;
+    // only allow admin to do this
+    if ADMIN.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    MAX_OFFSPRING.save(deps.storage, &max_offspring)?;
 
-    Ok(Response::new().add_attribute("offspring_address", &reply_info.address))
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Success,
+        message: None,
+    })?;
+    Ok(Response::new().set_data(resp_data))
 }
 
-/////////////////////////////////////// Query /////////////////////////////////////
-/// Returns Result<Binary, ContractError>
+/// Returns Result<Response, ContractError>
+///
+/// allows admin to reset the number of failed registrations
 ///
 /// # Arguments
 ///
-/// * `deps` - Deps containing all the contract's external dependencies
-/// * `_env` - Env of contract's environment
-/// * `msg`  - QueryMsg passed in with the query call
-#[entry_point]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
-    let response = match msg {
-        QueryMsg::ListMyOffspring {
-            permit,
-            address,
-            viewing_key,
-            filter,
-            start_page,
-            page_size,
-        } => try_list_my(
-            deps,
-            env,
-            permit,
-            address,
-            viewing_key,
-            filter,
-            start_page,
-            page_size,
-        ),
-        QueryMsg::ListActiveOffspring {
-            start_page,
-            page_size,
-        } => try_list_active(deps, start_page, page_size),
-        QueryMsg::ListInactiveOffspring {
-            start_page,
-            page_size,
-        } => try_list_inactive(deps, start_page, page_size),
-        QueryMsg::IsKeyValid {
-            address,
-            viewing_key,
-        } => try_validate_key(deps, &address, viewing_key),
-        QueryMsg::IsPermitValid { permit } => try_validate_permit(deps, env, permit),
-    };
-    pad_query_result(response, BLOCK_SIZE)
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+fn try_reset_failed_registrations(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // only allow admin to do this
+    if ADMIN.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    FAILED_REGISTRATIONS.save(deps.storage, &0)?;
+
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Success,
+        message: None,
+    })?;
+    Ok(Response::new().set_data(resp_data))
 }
 
-/// Returns Result<Binary, ContractError> indicating whether the address/key pair is valid
+/// Returns Result<Response, ContractError>
+///
+/// allows admin to change the block size that responses are padded to
 ///
 /// # Arguments
 ///
-/// * `deps`             - Deps containing all the contract's external dependencies
-/// * `permit`           - a reference to the permit offered for authentication
-/// * `contract_address` - String key used for authentication
-fn try_validate_permit(deps: Deps, env: Env, permit: Permit) -> Result<Binary, ContractError> {
-    let addr = is_permit_valid(deps, &permit, env.contract.address.to_string());
-    Ok(to_binary(&QueryAnswer::IsPermitValid {
-        is_valid: addr.is_ok(),
-        address: addr.ok(),
-    })?)
+/// * `deps`       - DepsMut containing all the contract's external dependencies
+/// * `info`       - Carries the info of who sent the message and how much native funds were sent along
+/// * `block_size` - new block size
+fn try_set_block_size(
+    deps: DepsMut,
+    info: MessageInfo,
+    block_size: usize,
+) -> Result<Response, ContractError> {
+    // only allow admin to do this
+    if ADMIN.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_block_size(block_size)?;
+    RESPONSE_BLOCK_SIZE.save(deps.storage, &block_size)?;
+
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Success,
+        message: None,
+    })?;
+    Ok(Response::new().set_data(resp_data))
 }
 
-/// Returns StdResult<Binary> indicating whether the address/key pair is valid
+/// Returns Result<Response, ContractError>
+///
+/// allows admin to let anyone, or only the owner, increment the counter of offspring that respect
+/// the factory's policy
 ///
 /// # Arguments
 ///
-/// * `deps`        - Deps containing all the contract's external dependencies
-/// * `address`     - a reference to the address whose key should be validated
-/// * `viewing_key` - String key used for authentication
-fn try_validate_key(
-    deps: Deps,
-    address: &str,
-    viewing_key: String,
-) -> Result<Binary, ContractError> {
-    Ok(to_binary(&QueryAnswer::IsKeyValid {
-        is_valid: is_key_valid(deps.storage, address, viewing_key),
-    })?)
+/// * `deps`    - DepsMut containing all the contract's external dependencies
+/// * `info`    - Carries the info of who sent the message and how much native funds were sent along
+/// * `allowed` - whether anyone can increment the counters
+fn try_set_public_increments(
+    deps: DepsMut,
+    info: MessageInfo,
+    allowed: bool,
+) -> Result<Response, ContractError> {
+    // only allow admin to do this
+    if ADMIN.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    PUBLIC_INCREMENTS_ALLOWED.save(deps.storage, &allowed)?;
+
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Success,
+        message: None,
+    })?;
+    Ok(Response::new().set_data(resp_data))
 }
 
-/// Returns Result<Binary, ContractError> listing the active offspring
+/// Returns Result<Response, ContractError>
+///
+/// allows admin to freeze or unfreeze offspring registrations and deactivations for maintenance
 ///
 /// # Arguments
 ///
-/// * `deps`       - Deps containing all the contract's external dependencies
-/// * `start_page` - optional start page for the offsprings returned and listed
-/// * `page_size`  - optional number of offspring to return in this page
-fn try_list_active(
-    deps: Deps,
-    start_page: Option<u32>,
-    page_size: Option<u32>,
-) -> Result<Binary, ContractError> {
-    Ok(to_binary(&QueryAnswer::ListActiveOffspring {
-        active: display_active_or_inactive_list(
-            deps.storage,
-            None,
-            FilterTypes::Active,
-            start_page,
-            page_size,
-        )?,
-    })?)
+/// * `deps`   - DepsMut containing all the contract's external dependencies
+/// * `info`   - Carries the info of who sent the message and how much native funds were sent along
+/// * `frozen` - true if offspring callbacks should be rejected
+fn try_set_callbacks_frozen(
+    deps: DepsMut,
+    info: MessageInfo,
+    frozen: bool,
+) -> Result<Response, ContractError> {
+    // only allow admin to do this
+    if ADMIN.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    CALLBACKS_FROZEN.save(deps.storage, &frozen)?;
+
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Success,
+        message: None,
+    })?;
+    Ok(Response::new()
+        .add_attribute("callbacks_frozen", frozen.to_string())
+        .set_data(resp_data))
 }
 
-/// Returns bool result of validating an address' viewing key
+/// Returns Result<(), ContractError>
+///
+/// makes sure that offspring callbacks are not frozen for maintenance
 ///
 /// # Arguments
 ///
-/// * `storage`     - a reference to the contract's storage
-/// * `account`     - a reference to the str whose key should be validated
-/// * `viewing_key` - String key used for authentication
-fn is_key_valid(storage: &dyn Storage, account: &str, viewing_key: String) -> bool {
-    ViewingKey::check(storage, account, &viewing_key).is_ok()
+/// * `storage` - a reference to the contract's storage
+fn enforce_callbacks_not_frozen(storage: &dyn Storage) -> Result<(), ContractError> {
+    if CALLBACKS_FROZEN.load(storage)? {
+        return Err(ContractError::CallbacksFrozen {});
+    }
+    Ok(())
 }
 
-/// Returns Result<Addr, ContractError>, the address of the permit's signer
+/// Returns Result<Response, ContractError>
+///
+/// allows admin to change the name that identifies the factory
 ///
 /// # Arguments
 ///
-/// * `deps`             - Deps containing all the contract's external dependencies
-/// * `permit`           - a reference to the permit offered for authentication
-/// * `contract_address` - String key used for authentication
-fn is_permit_valid(
-    deps: Deps,
-    permit: &Permit,
-    contract_address: String,
-) -> Result<Addr, ContractError> {
-    let address = validate(
-        deps,
-        PREFIX_REVOKED_PERMITS,
-        permit,
-        contract_address,
-        Some("secret"),
-    )?;
-    Ok(deps.api.addr_validate(&address)?)
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+/// * `name` - new name of the factory. Removes the name if omitted
+fn try_set_name(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: Option<String>,
+) -> Result<Response, ContractError> {
+    // only allow admin to do this
+    if ADMIN.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_name(&name)?;
+    NAME.save(deps.storage, &name)?;
+
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Success,
+        message: None,
+    })?;
+    Ok(Response::new().set_data(resp_data))
 }
 
-/// Returns Result<Binary, ContractError> listing the offspring with the address as its owner
+/// Returns Result<Response, ContractError>
+///
+/// allows admin to clear an owner's active/inactive lists and repopulate them with the registered
+/// offspring recorded as owned by them, leaving out renounced offspring. This iterates over all
+/// registered offspring.
 ///
 /// # Arguments
 ///
-/// * `deps`        - Deps containing all the contract's external dependencies
-/// * `env`         - Env of contract's environment
-/// * `permit`      - optional query permit to authenticate the query request. Either this or viewing key must be provided.
-/// * `address`     - Optional string address whose offspring should be listed. Either this or permit must be provided.
+/// * `deps`  - DepsMut containing all the contract's external dependencies
+/// * `info`  - Carries the info of who sent the message and how much native funds were sent along
+/// * `owner` - address of the owner whose lists are rebuilt
+fn try_rebuild_owner_index(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: String,
+) -> Result<Response, ContractError> {
+    // only allow admin to do this
+    if ADMIN.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let owners_active_store = OWNERS_ACTIVE.add_suffix(owner_addr.to_string().as_bytes());
+    let owners_inactive_store = OWNERS_INACTIVE.add_suffix(owner_addr.to_string().as_bytes());
+
+    // clear the owner's lists
+    for keyset in [&owners_active_store, &owners_inactive_store] {
+        let stale = keyset
+            .iter(deps.storage)?
+            .collect::<StdResult<Vec<Addr>>>()?;
+        for contract_addr in stale {
+            keyset.remove(deps.storage, &contract_addr)?;
+        }
+    }
+
+    // repopulate them from the recorded offspring info
+    let mut owned: Vec<Addr> = vec![];
+    for elem in OFFSPRING_STORAGE.iter(deps.storage)? {
+        let (contract_addr, offspring_info) = elem?;
+        // renounced offspring stay out of their former owner's lists
+        if offspring_info.owner == owner_addr && !OWNERLESS.contains(deps.storage, &contract_addr) {
+            owned.push(contract_addr);
+        }
+    }
+    for contract_addr in owned {
+        if ACTIVE_STORE.contains(deps.storage, &contract_addr) {
+            owners_active_store.insert(deps.storage, &contract_addr)?;
+        } else if INACTIVE_STORE.contains(deps.storage, &contract_addr) {
+            owners_inactive_store.insert(deps.storage, &contract_addr)?;
+        }
+    }
+
+    let (active, inactive) = owner_counts(deps.storage, &owner_addr)?;
+    if (active, inactive) == (0, 0) {
+        if OWNERS_SET.contains(deps.storage, &owner_addr) {
+            OWNERS_SET.remove(deps.storage, &owner_addr)?;
+        }
+    } else {
+        OWNERS_SET.insert(deps.storage, &owner_addr, &true)?;
+    }
+
+    let resp_data = to_binary(&HandleAnswer::RebuildOwnerIndex { active, inactive })?;
+    Ok(Response::new().set_data(resp_data))
+}
+
+/// Returns Result<(), ContractError>
+///
+/// makes sure that the block size responses are padded to is not zero
+///
+/// # Arguments
+///
+/// * `block_size` - block size being validated
+fn validate_block_size(block_size: usize) -> Result<(), ContractError> {
+    if block_size == 0 {
+        return Err(ContractError::InvalidBlockSize {});
+    }
+    Ok(())
+}
+
+/// Returns Result<(), ContractError>
+///
+/// makes sure that the factory name is not too long
+///
+/// # Arguments
+///
+/// * `name` - a reference to the optional name being validated
+fn validate_name(name: &Option<String>) -> Result<(), ContractError> {
+    if matches!(name, Some(name) if name.len() > MAX_NAME_LEN) {
+        return Err(ContractError::NameTooLong { max: MAX_NAME_LEN });
+    }
+    Ok(())
+}
+
+/// Returns StdResult<usize>
+///
+/// loads the block size responses are padded to
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+fn load_block_size(storage: &dyn Storage) -> StdResult<usize> {
+    RESPONSE_BLOCK_SIZE.load(storage)
+}
+
+/// Returns u32
+///
+/// resolves an optional page size, treating both None and zero as DEFAULT_PAGE_SIZE so that a
+/// page size of zero does not silently list nothing. Larger page sizes are capped at MAX_PAGE_SIZE
+///
+/// # Arguments
+///
+/// * `page_size` - optional page size given in a query
+fn page_size_or_default(page_size: Option<u32>) -> u32 {
+    match page_size {
+        None | Some(0) => DEFAULT_PAGE_SIZE,
+        Some(size) => size.min(MAX_PAGE_SIZE),
+    }
+}
+
+/// Returns StdResult<u64>
+///
+/// loads the block height of the factory's latest state change
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+fn load_last_modified_height(storage: &dyn Storage) -> StdResult<u64> {
+    LAST_MODIFIED_HEIGHT.load(storage)
+}
+
+/// Returns StdResult<ContractInfo>
+///
+/// loads the factory's own code hash and address
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `env`     - Env of contract's environment
+fn load_self_info(storage: &dyn Storage, env: Env) -> StdResult<ContractInfo> {
+    Ok(ContractInfo {
+        code_hash: SELF_CODE_HASH.load(storage)?,
+        address: env.contract.address,
+    })
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// allows admin to grant, change, or revoke an operator's permissions
+///
+/// # Arguments
+///
+/// * `deps`        - DepsMut containing all the contract's external dependencies
+/// * `info`        - Carries the info of who sent the message and how much native funds were sent along
+/// * `address`     - address of the operator
+/// * `permissions` - capabilities delegated to the operator. Removes the operator if none are granted
+fn try_set_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    permissions: OperatorPermissions,
+) -> Result<Response, ContractError> {
+    // only allow admin to do this
+    if ADMIN.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let operator = deps.api.addr_validate(&address)?;
+    if permissions.is_empty() {
+        OPERATORS.remove(deps.storage, &operator)?;
+    } else {
+        OPERATORS.insert(deps.storage, &operator, &permissions)?;
+    }
+
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Success,
+        message: None,
+    })?;
+    Ok(Response::new().set_data(resp_data))
+}
+
+/// Returns Result<(), ContractError>
+///
+/// makes sure that the sender is either the admin or an operator with the required permission
+///
+/// # Arguments
+///
+/// * `storage`        - a reference to the contract's storage
+/// * `sender`         - a reference to the address of the message sender
+/// * `has_permission` - returns whether the operator's permissions allow this action
+fn enforce_admin_or_operator(
+    storage: &dyn Storage,
+    sender: &Addr,
+    has_permission: fn(&OperatorPermissions) -> bool,
+) -> Result<(), ContractError> {
+    if ADMIN.load(storage)? == *sender {
+        return Ok(());
+    }
+    match OPERATORS.get(storage, sender) {
+        Some(permissions) if has_permission(&permissions) => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// create a viewing key. If the entropy is too short, no key is created and the reason is returned
+/// in a CreateViewingKeyError answer instead.
+///
+/// # Arguments
+///
+/// * `deps`    - DepsMut containing all the contract's external dependencies
+/// * `env`     - Env of contract's environment
+/// * `info`    - Carries the info of who sent the message and how much native funds were sent along
+/// * `entropy` - string to be used as an entropy source for randomization
+/// * `padding` - optional string that is mixed into the entropy
+fn try_create_key(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    entropy: String,
+    padding: Option<String>,
+) -> Result<Response, ContractError> {
+    if entropy.len() < MIN_ENTROPY_LENGTH {
+        let resp_data = to_binary(&HandleAnswer::CreateViewingKeyError {
+            msg: ContractError::InsufficientEntropy {
+                min: MIN_ENTROPY_LENGTH,
+            }
+            .to_string(),
+        })?;
+        return Ok(Response::new().set_data(resp_data));
+    }
+    let mut rng_entropy = entropy.into_bytes();
+    if let Some(padding) = padding {
+        rng_entropy.extend_from_slice(padding.as_bytes());
+    }
+    let key = ViewingKey::create(
+        deps.storage,
+        &info,
+        &env,
+        info.sender.as_str(),
+        &rng_entropy,
+    );
+
+    let resp_data = to_binary(&HandleAnswer::ViewingKey { key: key.clone() })?;
+    Ok(Response::new()
+        .add_attribute("viewing_key", key)
+        .set_data(resp_data))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// sets the viewing key
+///
+/// # Arguments
+///
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+/// * `key`  - string slice to be used as the viewing key
+fn try_set_key(deps: DepsMut, info: MessageInfo, key: &str) -> Result<Response, ContractError> {
+    ViewingKey::set(deps.storage, info.sender.as_str(), key);
+
+    Ok(Response::new().add_attribute("viewing_key", key))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// Revokes a all query permits with the given name
+///
+/// # Arguments
+///
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+/// * `key`  - string slice to be used as the viewing key
+fn revoke_permit(
+    deps: DepsMut,
+    info: MessageInfo,
+    permit_name: String,
+) -> Result<Response, ContractError> {
+    RevokedPermits::revoke_permit(
+        deps.storage,
+        PREFIX_REVOKED_PERMITS,
+        info.sender.as_ref(),
+        &permit_name,
+    );
+
+    Ok(Response::new())
+}
+
+/////////////////////////////////////// Reply /////////////////////////////////////
+/// Returns Result<Response, ContractError>
+///
+/// # Arguments
+///
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `msg` - QueryMsg passed in with the query call
+#[entry_point]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    LAST_MODIFIED_HEIGHT.save(deps.storage, &env.block.height)?;
+    match msg.id {
+        OFFSPRING_INSTANTIATE_REPLY_ID => handle_instantiate_reply(deps, env, msg),
+        id => Err(ContractError::UnexpectedReplyId { id }),
+    }
+}
+
+fn handle_instantiate_reply(
+    deps: DepsMut,
+    env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    enforce_callbacks_not_frozen(deps.storage)?;
+    // the entropy of the viewing key to create is not kept past this reply
+    let key_entropy = PENDING_KEY_ENTROPY.may_load(deps.storage)?;
+    PENDING_KEY_ENTROPY.remove(deps.storage);
+    let code_id = PENDING_CODE_ID.load(deps.storage)?;
+    PENDING_CODE_ID.remove(deps.storage);
+    let category = PENDING_CATEGORY.may_load(deps.storage)?;
+    PENDING_CATEGORY.remove(deps.storage);
+    // The parsing process below can be handled easier if one imports cw-plus
+    // See: https://github.com/CosmWasm/cw-plus/blob/main/packages/utils/src/parse_reply.rs
+    match msg.result {
+        SubMsgResult::Ok(s) => {
+            let reply_info: ReplyOffspringInfo = match s.data {
+                Some(bin) => from_binary(&bin)?,
+                None => {
+                    return Err(ContractError::CustomError {
+                        val: "Init didn't response with contract address".to_string(),
+                    })
+                }
+            };
+            // the offspring was created, so a rejected registration must revert its creation
+            enforce_can_register(deps.storage, &reply_info.address)?;
+            register_offspring_impl(deps, env, reply_info, code_id, key_entropy, category)
+        }
+        // only a failed instantiation is counted towards stopping the factory
+        SubMsgResult::Err(e) => {
+            record_failed_registration(deps, ContractError::CustomError { val: e })
+        }
+    }
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// counts a failed offspring instantiation and stops the factory once more instantiations failed
+/// than it tolerates. If the factory does not tolerate any failures, the error is returned instead
+/// so that the CreateOffspring transaction fails.
+///
+/// # Arguments
+///
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `err`  - the reason the registration failed
+fn record_failed_registration(
+    deps: DepsMut,
+    err: ContractError,
+) -> Result<Response, ContractError> {
+    let max = match MAX_FAILED_REGISTRATIONS.load(deps.storage)? {
+        Some(max) => max,
+        None => return Err(err),
+    };
+    let failures = FAILED_REGISTRATIONS.load(deps.storage)? + 1;
+    FAILED_REGISTRATIONS.save(deps.storage, &failures)?;
+
+    let mut response = Response::new().add_attribute("registration_error", err.to_string());
+    if failures > max && !IS_STOPPED.load(deps.storage)? {
+        IS_STOPPED.save(deps.storage, &true)?;
+        response = response.add_attribute("factory_stopped", "true");
+    }
+
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Failure,
+        message: Some(err.to_string()),
+    })?;
+    Ok(response.set_data(resp_data))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// allows admin to register an orphaned offspring, one that was instantiated but is missing from
+/// the factory's lists. Its last count is recorded as 0 until it reports a count, and it is
+/// recorded with the current offspring code id.
+///
+/// # Arguments
+///
+/// * `deps`      - DepsMut containing all the contract's external dependencies
+/// * `env`       - Env of contract's environment
+/// * `info`      - Carries the info of who sent the message and how much native funds were sent along
+/// * `offspring` - address of the offspring
+/// * `owner`     - address of the owner of the offspring
+/// * `label`     - label the offspring was instantiated with
+/// * `code_hash` - code hash of the offspring
+fn try_manual_register(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offspring: String,
+    owner: String,
+    label: String,
+    code_hash: String,
+) -> Result<Response, ContractError> {
+    // only allow admin to do this
+    if ADMIN.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let address = deps.api.addr_validate(&offspring)?;
+    enforce_can_register(deps.storage, &address)?;
+    let reply_info = ReplyOffspringInfo {
+        label,
+        title: None,
+        owner: deps.api.addr_validate(&owner)?,
+        address,
+        code_hash,
+        count: 0,
+        report_count: false,
+        correlation_id: None,
+    };
+
+    let resp_data = to_binary(&HandleAnswer::Status {
+        status: ResponseStatus::Success,
+        message: None,
+    })?;
+    let code_id = OFFSPRING_CODE.load(deps.storage)?.code_id;
+    Ok(register_offspring_impl(deps, env, reply_info, code_id, None, None)?.set_data(resp_data))
+}
+
+/// Returns Result<(), ContractError>
+///
+/// rejects the registration of an offspring that is already registered, or that would exceed the
+/// maximum number of offspring the factory creates
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `address` - a reference to the address of the offspring to register
+fn enforce_can_register(storage: &dyn Storage, address: &Addr) -> Result<(), ContractError> {
+    if OFFSPRING_STORAGE.contains(storage, address) {
+        return Err(ContractError::AlreadyRegistered {
+            address: address.to_string(),
+        });
+    }
+    if let Some(max) = MAX_OFFSPRING.load(storage)? {
+        if TOTAL_CREATED.load(storage)? >= max {
+            return Err(ContractError::AtCapacity { max });
+        }
+    }
+    Ok(())
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// Registers the calling offspring by saving its info and adding it to the appropriate lists
+///
+/// # Arguments
+///
+/// * `deps`        - DepsMut containing all the contract's external dependencies
+/// * `env`         - Env of contract's environment
+/// * `reply_info`  - reference to ReplyOffspringInfo of the offspring that is trying to register
+/// * `code_id`     - code id the offspring was instantiated with
+/// * `key_entropy` - optional entropy of a viewing key to create for the owner once the offspring is
+///   registered. The key is returned in the response data
+/// * `category`    - optional category the offspring was created with
+fn register_offspring_impl(
+    deps: DepsMut,
+    env: Env,
+    reply_info: ReplyOffspringInfo,
+    code_id: u64,
+    key_entropy: Option<String>,
+    category: Option<String>,
+) -> Result<Response, ContractError> {
+    // remember the category for ListCategories
+    if let Some(category) = &category {
+        CATEGORIES.insert(deps.storage, category, &true)?;
+    }
+    // convert register offspring info to storage format
+    let offspring = reply_info.to_store_offspring_info(code_id, env.block.height, category);
+
+    // save the offspring info
+    OFFSPRING_STORAGE.insert(deps.storage, &reply_info.address, &offspring)?;
+
+    if REQUIRE_ACTIVATION.load(deps.storage)? {
+        // list as inactive until the owner activates it
+        PENDING_ACTIVATION.insert(deps.storage, &reply_info.address)?;
+        INACTIVE_STORE.insert(deps.storage, &reply_info.address)?;
+        OWNERS_INACTIVE
+            .add_suffix(reply_info.owner.to_string().as_bytes())
+            .insert(deps.storage, &reply_info.address)?;
+    } else {
+        // add active list
+        ACTIVE_STORE.insert(deps.storage, &reply_info.address)?;
+        // add to owner's active list
+        OWNERS_ACTIVE
+            .add_suffix(reply_info.owner.to_string().as_bytes())
+            .insert(deps.storage, &reply_info.address)?;
+    }
+    // add to the distinct owners
+    OWNERS_SET.insert(deps.storage, &reply_info.owner, &true)?;
+    // count it towards the lifetime total
+    let total_created = TOTAL_CREATED.load(deps.storage)?;
+    TOTAL_CREATED.save(deps.storage, &(total_created + 1))?;
+
+    // the offspring is registered, so the owner's new key can replace their current one
+    let viewing_key = key_entropy.map(|entropy| {
+        let info = MessageInfo {
+            sender: reply_info.owner.clone(),
+            funds: vec![],
+        };
+        ViewingKey::create(
+            deps.storage,
+            &info,
+            &env,
+            reply_info.owner.as_str(),
+            entropy.as_bytes(),
+        )
+    });
+
+    // the reply's data replaces the CreateOffspring response data
+    let resp_data = to_binary(&HandleAnswer::OffspringCreated {
+        address: reply_info.address.clone(),
+        viewing_key,
+    })?;
+    let mut response = Response::new()
+        .add_attributes(offspring_attributes("register_offspring", &offspring, &env))
+        .set_data(resp_data);
+    if let Some(correlation_id) = reply_info.correlation_id {
+        response = response.add_attribute("correlation_id", correlation_id);
+    }
+
+    Ok(response)
+}
+
+/// Returns Vec<Attribute>, the attributes logged for every action on an offspring. See ExecuteMsg
+/// for the schema.
+///
+/// # Arguments
+///
+/// * `action`    - name of the action
+/// * `offspring` - a reference to the stored info of the offspring
+/// * `env`       - a reference to the Env of contract's environment
+fn offspring_attributes(action: &str, offspring: &StoreOffspringInfo, env: &Env) -> Vec<Attribute> {
+    vec![
+        Attribute::new("action", action),
+        Attribute::new("offspring", offspring.contract.address.as_str()),
+        Attribute::new("owner", offspring.owner.as_str()),
+        Attribute::new("code_id", offspring.code_id.to_string()),
+        Attribute::new("height", env.block.height.to_string()),
+    ]
+}
+
+/////////////////////////////////////// Query /////////////////////////////////////
+/// Returns Result<Binary, ContractError>
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+/// * `_env` - Env of contract's environment
+/// * `msg`  - QueryMsg passed in with the query call
+#[entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    let block_size = load_block_size(deps.storage)?;
+    let response = match msg {
+        QueryMsg::ListMyOffspring {
+            permit,
+            address,
+            viewing_key,
+            filter,
+            start_page,
+            page_size,
+            inactive_start_page,
+            inactive_page_size,
+        } => try_list_my(
+            deps,
+            env,
+            permit,
+            address,
+            viewing_key,
+            filter,
+            start_page,
+            page_size,
+            inactive_start_page,
+            inactive_page_size,
+        ),
+        QueryMsg::ListMyOffspringFrom {
+            address,
+            viewing_key,
+            after,
+            limit,
+        } => try_list_my_from(deps, address, viewing_key, after, limit),
+        QueryMsg::SearchMyOffspring {
+            address,
+            viewing_key,
+            label_contains,
+            filter,
+            start_page,
+            page_size,
+        } => try_search_my(
+            deps,
+            address,
+            viewing_key,
+            label_contains,
+            filter,
+            start_page,
+            page_size,
+        ),
+        QueryMsg::BatchCounts {
+            addresses,
+            address,
+            viewing_key,
+        } => try_batch_counts(deps, addresses, address, viewing_key),
+        QueryMsg::OwnerSummary {
+            address,
+            viewing_key,
+        } => try_owner_summary(deps, address, viewing_key),
+        QueryMsg::ValidateAndCount {
+            address,
+            viewing_key,
+        } => try_validate_and_count(deps, address, viewing_key),
+        QueryMsg::ListActiveOffspring {
+            start_page,
+            page_size,
+        } => try_list_active(deps, start_page, page_size),
+        QueryMsg::ListInactiveOffspring {
+            start_page,
+            page_size,
+        } => try_list_inactive(deps, start_page, page_size),
+        QueryMsg::ListAllOffspring {
+            start_page,
+            page_size,
+        } => try_list_all(deps, start_page, page_size),
+        QueryMsg::ListLabels {
+            start_page,
+            page_size,
+        } => try_list_labels(deps, start_page, page_size),
+        QueryMsg::ListByHeightRange {
+            from,
+            to,
+            start_page,
+            page_size,
+        } => try_list_by_height_range(deps, from, to, start_page, page_size),
+        QueryMsg::ListByCategory {
+            category,
+            start_page,
+            page_size,
+        } => try_list_by_category(deps, category, start_page, page_size),
+        QueryMsg::ListCategories {
+            start_page,
+            page_size,
+        } => try_list_categories(deps, start_page, page_size),
+        QueryMsg::ListOwnerless {
+            start_page,
+            page_size,
+        } => try_list_ownerless(deps, start_page, page_size),
+        QueryMsg::ListForOwners {
+            owners,
+            filter,
+            admin_viewing_key,
+        } => try_list_for_owners(deps, owners, filter, admin_viewing_key),
+        QueryMsg::ListOwners {
+            admin_viewing_key,
+            start_page,
+            page_size,
+        } => try_list_owners(deps, admin_viewing_key, start_page, page_size),
+        QueryMsg::AdminListAll {
+            admin_viewing_key,
+            start_page,
+            page_size,
+        } => try_admin_list_all(deps, admin_viewing_key, start_page, page_size),
+        QueryMsg::OwnerCount {} => try_owner_count(deps),
+        QueryMsg::IsAdmin { address } => try_is_admin(deps, address),
+        QueryMsg::Config {} => try_config(deps),
+        QueryMsg::Stats {} => try_stats(deps),
+        QueryMsg::VersionBreakdown {} => try_version_breakdown(deps),
+        QueryMsg::TotalCount {} => try_total_count(deps),
+        QueryMsg::TopOffspring { limit } => try_top_offspring(deps, limit),
+        QueryMsg::OffspringCode {} => try_offspring_code(deps),
+        QueryMsg::SelfInfo {} => try_self_info(deps, env),
+        QueryMsg::Capabilities {} => try_capabilities(deps),
+        QueryMsg::PreviewCreate { label } => try_preview_create(deps, label),
+        QueryMsg::IsKeyValid {
+            address,
+            viewing_key,
+        } => try_validate_key(deps, &address, viewing_key),
+        QueryMsg::IsKeyValidFor {
+            viewing_key,
+            candidates,
+        } => try_validate_key_for(deps, viewing_key, candidates),
+        QueryMsg::AreKeysValid { pairs } => try_validate_keys(deps, pairs),
+        QueryMsg::IsPermitValid { permit } => try_validate_permit(deps, env, permit),
+        QueryMsg::GetMyInfo { offspring } => try_get_my_info(deps, offspring),
+        QueryMsg::OffspringInfo { offspring } => try_offspring_info(deps, offspring),
+        QueryMsg::IsOffspringActive { address } => try_is_offspring_active(deps, address),
+        QueryMsg::PublicIncrementsAllowed {} => try_public_increments_allowed(deps),
+    };
+    pad_query_result(response, block_size)
+}
+
+/// Returns Result<Binary, ContractError> listing the offspring of several owners
+///
+/// # Arguments
+///
+/// * `deps`              - Deps containing all the contract's external dependencies
+/// * `owners`            - addresses whose offspring are listed
+/// * `filter`            - optional choice of active or inactive offspring to list
+/// * `admin_viewing_key` - String key used to authenticate the admin
+fn try_list_for_owners(
+    deps: Deps,
+    owners: Vec<String>,
+    filter: Option<FilterTypes>,
+    admin_viewing_key: String,
+) -> Result<Binary, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if !is_key_valid(deps.storage, admin.as_str(), admin_viewing_key) {
+        return Err(ContractError::ViewingKeyOrUnauthorized {});
+    }
+    if owners.len() > MAX_OWNER_BATCH {
+        return Err(ContractError::BatchTooLarge {
+            max: MAX_OWNER_BATCH,
+        });
+    }
+    // if no filter default to ALL
+    let types = filter.unwrap_or(FilterTypes::All);
+    let list_active = types == FilterTypes::Active || types == FilterTypes::All;
+    let list_inactive = types == FilterTypes::Inactive || types == FilterTypes::All;
+
+    let mut listed: Vec<OwnerOffspring> = vec![];
+    for owner in owners {
+        let owner = deps.api.addr_validate(&owner)?;
+        let mut active = None;
+        let mut active_has_more = None;
+        let mut inactive = None;
+        let mut inactive_has_more = None;
+        if list_active {
+            let (list, has_more, _) = display_active_or_inactive_list(
+                deps.storage,
+                Some(owner.clone()),
+                FilterTypes::Active,
+                None,
+                None,
+            )?;
+            active = Some(list);
+            active_has_more = Some(has_more);
+        }
+        if list_inactive {
+            let (list, has_more, _) = display_active_or_inactive_list(
+                deps.storage,
+                Some(owner.clone()),
+                FilterTypes::Inactive,
+                None,
+                None,
+            )?;
+            inactive = Some(list);
+            inactive_has_more = Some(has_more);
+        }
+        listed.push(OwnerOffspring {
+            owner,
+            active,
+            active_has_more,
+            inactive,
+            inactive_has_more,
+        });
+    }
+
+    Ok(to_binary(&QueryAnswer::ListForOwners { owners: listed })?)
+}
+
+/// Returns Result<Binary, ContractError> listing the addresses that own offspring
+///
+/// # Arguments
+///
+/// * `deps`              - Deps containing all the contract's external dependencies
+/// * `admin_viewing_key` - String key used to authenticate the admin
+/// * `start_page`        - optional start page for the owners returned and listed
+/// * `page_size`         - optional number of owners to return in this page
+fn try_list_owners(
+    deps: Deps,
+    admin_viewing_key: String,
+    start_page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<Binary, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if !is_key_valid(deps.storage, admin.as_str(), admin_viewing_key) {
+        return Err(ContractError::ViewingKeyOrUnauthorized {});
+    }
+    let start_page = start_page.unwrap_or(0);
+    let size = page_size_or_default(page_size);
+    let mut owners: Vec<Addr> = vec![];
+
+    let mut paginated_keys_iter = OWNERS_SET
+        .iter_keys(deps.storage)?
+        .skip((start_page as usize) * (size as usize))
+        .take(size as usize);
+
+    loop {
+        let may_next_elem = paginated_keys_iter.next();
+        if let Some(elem) = may_next_elem {
+            owners.push(elem?);
+        } else {
+            break;
+        }
+    }
+
+    Ok(to_binary(&QueryAnswer::ListOwners {
+        owners,
+        last_modified_height: load_last_modified_height(deps.storage)?,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the number of distinct addresses that own
+/// offspring
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+fn try_owner_count(deps: Deps) -> Result<Binary, ContractError> {
+    Ok(to_binary(&QueryAnswer::OwnerCount {
+        count: OWNERS_SET.get_len(deps.storage)?,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying whether the address is the admin
+///
+/// # Arguments
+///
+/// * `deps`    - Deps containing all the contract's external dependencies
+/// * `address` - String address being checked
+fn try_is_admin(deps: Deps, address: String) -> Result<Binary, ContractError> {
+    Ok(to_binary(&QueryAnswer::IsAdmin {
+        is_admin: ADMIN.load(deps.storage)?.as_str() == address,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the factory's configuration
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+fn try_config(deps: Deps) -> Result<Binary, ContractError> {
+    let max_offspring = MAX_OFFSPRING.load(deps.storage)?;
+    let total_created = TOTAL_CREATED.load(deps.storage)?;
+
+    Ok(to_binary(&QueryAnswer::Config {
+        is_stopped: IS_STOPPED.load(deps.storage)?,
+        count_bounds: COUNT_BOUNDS.load(deps.storage)?,
+        allow_negative_count: ALLOW_NEGATIVE_COUNT.load(deps.storage)?,
+        require_activation: REQUIRE_ACTIVATION.load(deps.storage)?,
+        max_offspring,
+        remaining_capacity: max_offspring.map(|max| max.saturating_sub(total_created)),
+        block_size: load_block_size(deps.storage)?,
+        max_failed_registrations: MAX_FAILED_REGISTRATIONS.load(deps.storage)?,
+        failed_registrations: FAILED_REGISTRATIONS.load(deps.storage)?,
+        public_increments_allowed: PUBLIC_INCREMENTS_ALLOWED.load(deps.storage)?,
+        name: NAME.load(deps.storage)?,
+        callbacks_frozen: CALLBACKS_FROZEN.load(deps.storage)?,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying offspring statistics
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+fn try_stats(deps: Deps) -> Result<Binary, ContractError> {
+    Ok(to_binary(&QueryAnswer::Stats {
+        active_count: ACTIVE_STORE.get_len(deps.storage)?,
+        inactive_count: INACTIVE_STORE.get_len(deps.storage)?,
+        total_ever_created: TOTAL_CREATED.load(deps.storage)?,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the number of offspring per code id. This
+/// iterates over all registered offspring.
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+fn try_version_breakdown(deps: Deps) -> Result<Binary, ContractError> {
+    let mut versions: BTreeMap<u64, u32> = BTreeMap::new();
+    for elem in OFFSPRING_STORAGE.iter(deps.storage)? {
+        let (_, offspring_info) = elem?;
+        *versions.entry(offspring_info.code_id).or_insert(0) += 1;
+    }
+
+    Ok(to_binary(&QueryAnswer::VersionBreakdown {
+        versions: versions.into_iter().collect(),
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the sum of the last reported counts of the
+/// offspring that report their count. This iterates over all registered offspring.
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+fn try_total_count(deps: Deps) -> Result<Binary, ContractError> {
+    let mut total: i64 = 0;
+    for elem in OFFSPRING_STORAGE.iter(deps.storage)? {
+        let (_, offspring_info) = elem?;
+        if offspring_info.reports_count {
+            total = total.saturating_add(offspring_info.last_count as i64);
+        }
+    }
+
+    Ok(to_binary(&QueryAnswer::TotalCount { total })?)
+}
+
+/// Returns Result<Binary, ContractError> listing the offspring with the highest reported counts.
+/// Offspring that do not report their count are left out. This iterates over all registered
+/// offspring.
+///
+/// # Arguments
+///
+/// * `deps`  - Deps containing all the contract's external dependencies
+/// * `limit` - optional number of offspring to list
+fn try_top_offspring(deps: Deps, limit: Option<u32>) -> Result<Binary, ContractError> {
+    let limit = limit.unwrap_or(MAX_TOP_LIMIT).min(MAX_TOP_LIMIT) as usize;
+    let mut counts: Vec<OffspringCount> = vec![];
+    for elem in OFFSPRING_STORAGE.iter(deps.storage)? {
+        let (address, offspring_info) = elem?;
+        if offspring_info.reports_count {
+            counts.push(OffspringCount {
+                address,
+                count: offspring_info.last_count,
+            });
+        }
+    }
+    counts.sort_by_key(|offspring| std::cmp::Reverse(offspring.count));
+    counts.truncate(limit);
+
+    Ok(to_binary(&QueryAnswer::TopOffspring { offspring: counts })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the current offspring code info
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+fn try_offspring_code(deps: Deps) -> Result<Binary, ContractError> {
+    Ok(to_binary(&QueryAnswer::OffspringCode {
+        code_info: OFFSPRING_CODE.load(deps.storage)?,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the factory's own address and code hash
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+/// * `env`  - Env of contract's environment
+fn try_self_info(deps: Deps, env: Env) -> Result<Binary, ContractError> {
+    Ok(to_binary(&QueryAnswer::SelfInfo {
+        contract: load_self_info(deps.storage, env)?,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the features this factory supports
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+fn try_capabilities(deps: Deps) -> Result<Binary, ContractError> {
+    Ok(to_binary(&QueryAnswer::Capabilities {
+        capabilities: Capabilities {
+            schema_version: SCHEMA_VERSION,
+            permits: true,
+            auto_create_key: true,
+            code_versions: true,
+            creation_enabled: !IS_STOPPED.load(deps.storage)?,
+            callbacks_enabled: !CALLBACKS_FROZEN.load(deps.storage)?,
+            public_increments_allowed: PUBLIC_INCREMENTS_ALLOWED.load(deps.storage)?,
+        },
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the code info and instantiate label that
+/// CreateOffspring would use. The offspring's address can not be predicted, so this is only meant
+/// for verifying the factory's configuration.
+///
+/// # Arguments
+///
+/// * `deps`  - Deps containing all the contract's external dependencies
+/// * `label` - label that would be given to CreateOffspring
+fn try_preview_create(deps: Deps, label: String) -> Result<Binary, ContractError> {
+    Ok(to_binary(&QueryAnswer::PreviewCreate {
+        code_info: OFFSPRING_CODE.load(deps.storage)?,
+        // offspring are instantiated with the label as given
+        label,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> indicating whether the address/key pair is valid
+///
+/// # Arguments
+///
+/// * `deps`             - Deps containing all the contract's external dependencies
+/// * `permit`           - a reference to the permit offered for authentication
+/// * `contract_address` - String key used for authentication
+fn try_validate_permit(deps: Deps, env: Env, permit: Permit) -> Result<Binary, ContractError> {
+    let addr = is_permit_valid(deps, &permit, env.contract.address.to_string());
+    Ok(to_binary(&QueryAnswer::IsPermitValid {
+        is_valid: addr.is_ok(),
+        address: addr.ok(),
+    })?)
+}
+
+/// Returns StdResult<Binary> indicating whether the address/key pair is valid
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `address`     - a reference to the address whose key should be validated
+/// * `viewing_key` - String key used for authentication
+fn try_validate_key(
+    deps: Deps,
+    address: &str,
+    viewing_key: String,
+) -> Result<Binary, ContractError> {
+    let is_valid = is_key_valid(deps.storage, address, viewing_key);
+    let address = if is_valid {
+        Some(deps.api.addr_validate(address)?)
+    } else {
+        None
+    };
+    Ok(to_binary(&QueryAnswer::IsKeyValid { is_valid, address })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the first candidate address the viewing key
+/// is valid for
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `viewing_key` - String key used for authentication
+/// * `candidates`  - addresses the key may belong to
+fn try_validate_key_for(
+    deps: Deps,
+    viewing_key: String,
+    candidates: Vec<String>,
+) -> Result<Binary, ContractError> {
+    if candidates.len() > MAX_KEY_BATCH {
+        return Err(ContractError::BatchTooLarge { max: MAX_KEY_BATCH });
+    }
+    let address = candidates
+        .into_iter()
+        .find(|candidate| is_key_valid(deps.storage, candidate, viewing_key.clone()))
+        .map(|candidate| deps.api.addr_validate(&candidate))
+        .transpose()?;
+
+    Ok(to_binary(&QueryAnswer::IsKeyValidFor { address })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the validity of several address/key pairs
+///
+/// # Arguments
+///
+/// * `deps`  - Deps containing all the contract's external dependencies
+/// * `pairs` - (address, viewing key) pairs to authenticate
+fn try_validate_keys(deps: Deps, pairs: Vec<(String, String)>) -> Result<Binary, ContractError> {
+    if pairs.len() > MAX_KEY_BATCH {
+        return Err(ContractError::BatchTooLarge { max: MAX_KEY_BATCH });
+    }
+    let is_valid = pairs
+        .into_iter()
+        .map(|(address, viewing_key)| is_key_valid(deps.storage, &address, viewing_key))
+        .collect();
+
+    Ok(to_binary(&QueryAnswer::AreKeysValid { is_valid })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the info recorded about an offspring
+///
+/// # Arguments
+///
+/// * `deps`      - Deps containing all the contract's external dependencies
+/// * `offspring` - address of the offspring
+fn try_get_my_info(deps: Deps, offspring: String) -> Result<Binary, ContractError> {
+    let offspring_addr = deps.api.addr_validate(&offspring)?;
+    let info = OFFSPRING_STORAGE
+        .get(deps.storage, &offspring_addr)
+        .ok_or_else(|| ContractError::OffspringNotFound {
+            address: offspring_addr.to_string(),
+        })?;
+    Ok(to_binary(&QueryAnswer::GetMyInfo {
+        info: info.to_public_offspring_info(),
+        is_active: ACTIVE_STORE.contains(deps.storage, &offspring_addr),
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying whether an offspring is registered and active
+///
+/// # Arguments
+///
+/// * `deps`      - Deps containing all the contract's external dependencies
+/// * `offspring` - address of the offspring
+fn try_offspring_info(deps: Deps, offspring: String) -> Result<Binary, ContractError> {
+    let offspring_addr = deps.api.addr_validate(&offspring)?;
+    Ok(to_binary(&QueryAnswer::OffspringInfo {
+        is_registered: OFFSPRING_STORAGE.contains(deps.storage, &offspring_addr),
+        is_active: ACTIVE_STORE.contains(deps.storage, &offspring_addr),
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying whether an offspring is active, or None if it
+/// is not registered
+///
+/// # Arguments
+///
+/// * `deps`    - Deps containing all the contract's external dependencies
+/// * `address` - address of the offspring
+fn try_is_offspring_active(deps: Deps, address: String) -> Result<Binary, ContractError> {
+    let offspring_addr = deps.api.addr_validate(&address)?;
+    let is_active = if ACTIVE_STORE.contains(deps.storage, &offspring_addr) {
+        Some(true)
+    } else if INACTIVE_STORE.contains(deps.storage, &offspring_addr) {
+        Some(false)
+    } else {
+        None
+    };
+    Ok(to_binary(&QueryAnswer::IsOffspringActive { is_active })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying whether offspring that respect the factory's
+/// policy let anyone increment their counter
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+fn try_public_increments_allowed(deps: Deps) -> Result<Binary, ContractError> {
+    Ok(to_binary(&QueryAnswer::PublicIncrementsAllowed {
+        is_allowed: PUBLIC_INCREMENTS_ALLOWED.load(deps.storage)?,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> listing the active offspring
+///
+/// # Arguments
+///
+/// * `deps`       - Deps containing all the contract's external dependencies
+/// * `start_page` - optional start page for the offsprings returned and listed
+/// * `page_size`  - optional number of offspring to return in this page
+fn try_list_active(
+    deps: Deps,
+    start_page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<Binary, ContractError> {
+    let start_page = start_page.unwrap_or(0);
+    let page_size = page_size_or_default(page_size);
+    let (items, has_more, orphans) = display_active_or_inactive_list(
+        deps.storage,
+        None,
+        FilterTypes::Active,
+        Some(start_page),
+        Some(page_size),
+    )?;
+    Ok(to_binary(&QueryAnswer::ListActiveOffspring {
+        active: Page {
+            items,
+            start_page,
+            page_size,
+            total: ACTIVE_STORE.get_len(deps.storage)?,
+            has_more,
+        },
+        orphans,
+        last_modified_height: load_last_modified_height(deps.storage)?,
+    })?)
+}
+
+/// Returns bool result of validating an address' viewing key
+///
+/// # Arguments
+///
+/// * `storage`     - a reference to the contract's storage
+/// * `account`     - a reference to the str whose key should be validated
+/// * `viewing_key` - String key used for authentication
+fn is_key_valid(storage: &dyn Storage, account: &str, viewing_key: String) -> bool {
+    ViewingKey::check(storage, account, &viewing_key).is_ok()
+}
+
+/// Returns Result<Addr, ContractError>, the address of the permit's signer
+///
+/// # Arguments
+///
+/// * `deps`             - Deps containing all the contract's external dependencies
+/// * `permit`           - a reference to the permit offered for authentication
+/// * `contract_address` - String key used for authentication
+fn is_permit_valid(
+    deps: Deps,
+    permit: &Permit,
+    contract_address: String,
+) -> Result<Addr, ContractError> {
+    let address = validate(
+        deps,
+        PREFIX_REVOKED_PERMITS,
+        permit,
+        contract_address,
+        Some("secret"),
+    )?;
+    Ok(deps.api.addr_validate(&address)?)
+}
+
+/// Returns Result<Addr, ContractError>, the authenticated address of the querier
+///
+/// authenticates the querier with either a viewing key or a permit. An address/viewing key pair
+/// takes precedence, in which case the permit is ignored.
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `env`         - Env of contract's environment
+/// * `permit`      - optional query permit to authenticate the query request. Either this or viewing key must be provided.
+/// * `address`     - Optional string address of the querier. Either this or permit must be provided.
 /// * `viewing_key` - Optional string key used to authenticate the query. Either this or permit must be provided.
-/// * `filter`      - optional choice of display filters
-/// * `start_page`  - optional start page for the offsprings returned and listed
-/// * `page_size`   - optional number of offspring to return in this page
+fn authenticate_querier(
+    deps: Deps,
+    env: Env,
+    permit: Option<Permit>,
+    address: Option<String>,
+    viewing_key: Option<String>,
+) -> Result<Addr, ContractError> {
+    if let (Some(address), Some(viewing_key)) = (address, viewing_key) {
+        let addr = deps.api.addr_validate(&address)?;
+        // if key matches
+        if !is_key_valid(deps.storage, addr.as_str(), viewing_key) {
+            return Err(ContractError::ViewingKeyOrUnauthorized {});
+        }
+        Ok(addr)
+    } else if let Some(permit) = permit {
+        is_permit_valid(deps, &permit, env.contract.address.to_string())
+    } else {
+        Err(ContractError::Unauthorized {})
+    }
+}
+
+/// Returns Result<Binary, ContractError> listing the offspring with the address as its owner
+///
+/// # Arguments
+///
+/// * `deps`                - Deps containing all the contract's external dependencies
+/// * `env`                 - Env of contract's environment
+/// * `permit`              - optional query permit to authenticate the query request. Either this or viewing key must be provided.
+/// * `address`             - Optional string address whose offspring should be listed. Either this or permit must be provided.
+/// * `viewing_key`         - Optional string key used to authenticate the query. Either this or permit must be provided.
+/// * `filter`              - optional choice of display filters
+/// * `start_page`          - optional start page for the offsprings returned and listed
+/// * `page_size`           - optional number of offspring to return in this page
+/// * `inactive_start_page` - optional start page for the inactive offspring. Defaults to start_page
+/// * `inactive_page_size`  - optional number of inactive offspring to return. Defaults to page_size
 #[allow(clippy::too_many_arguments)]
 fn try_list_my(
     deps: Deps,
-    env: Env,
-    permit: Option<Permit>,
-    address: Option<String>,
-    viewing_key: Option<String>,
-    filter: Option<FilterTypes>,
+    env: Env,
+    permit: Option<Permit>,
+    address: Option<String>,
+    viewing_key: Option<String>,
+    filter: Option<FilterTypes>,
+    start_page: Option<u32>,
+    page_size: Option<u32>,
+    inactive_start_page: Option<u32>,
+    inactive_page_size: Option<u32>,
+) -> Result<Binary, ContractError> {
+    let addr = authenticate_querier(deps, env, permit, address, viewing_key)?;
+    let mut active_list: Option<Vec<PublicOffspringInfo>> = None;
+    let mut active_has_more: Option<bool> = None;
+    let mut inactive_list: Option<Vec<PublicOffspringInfo>> = None;
+    let mut inactive_has_more: Option<bool> = None;
+    let mut orphans: Vec<Addr> = vec![];
+    // if no filter default to ALL
+    let types = filter.unwrap_or(FilterTypes::All);
+
+    // list the active offspring
+    if types == FilterTypes::Active || types == FilterTypes::All {
+        let (list, has_more, mut active_orphans) = display_active_or_inactive_list(
+            deps.storage,
+            Some(addr.clone()),
+            FilterTypes::Active,
+            start_page,
+            page_size,
+        )?;
+        active_list = Some(list);
+        active_has_more = Some(has_more);
+        orphans.append(&mut active_orphans);
+    }
+    // list the inactive offspring
+    if types == FilterTypes::Inactive || types == FilterTypes::All {
+        let (list, has_more, mut inactive_orphans) = display_active_or_inactive_list(
+            deps.storage,
+            Some(addr),
+            FilterTypes::Inactive,
+            inactive_start_page.or(start_page),
+            inactive_page_size.or(page_size),
+        )?;
+        inactive_list = Some(list);
+        inactive_has_more = Some(has_more);
+        orphans.append(&mut inactive_orphans);
+    }
+
+    Ok(to_binary(&QueryAnswer::ListMyOffspring {
+        active: active_list,
+        active_has_more,
+        inactive: inactive_list,
+        inactive_has_more,
+        orphans,
+        last_modified_height: load_last_modified_height(deps.storage)?,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> listing the offspring the address owns, active ones
+/// first, that come after the given cursor. If the cursor is no longer among the address'
+/// offspring, for example because it was transferred, nothing is listed instead of failing
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `address`     - String address whose offspring are listed
+/// * `viewing_key` - String key used to authenticate the query
+/// * `after`       - optional address of the offspring to resume after
+/// * `limit`       - optional number of offspring to return
+fn try_list_my_from(
+    deps: Deps,
+    address: String,
+    viewing_key: String,
+    after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Binary, ContractError> {
+    let addr = deps.api.addr_validate(&address)?;
+    if !is_key_valid(deps.storage, addr.as_str(), viewing_key) {
+        return Err(ContractError::ViewingKeyOrUnauthorized {});
+    }
+    let after = after.map(|a| deps.api.addr_validate(&a)).transpose()?;
+    let limit = page_size_or_default(limit) as usize;
+
+    let owners_active_store = OWNERS_ACTIVE.add_suffix(addr.to_string().as_bytes());
+    let owners_inactive_store = OWNERS_INACTIVE.add_suffix(addr.to_string().as_bytes());
+    let mut keys_iter = owners_active_store
+        .iter(deps.storage)?
+        .chain(owners_inactive_store.iter(deps.storage)?);
+
+    // resume right after the cursor. A missing cursor exhausts the iterator, so nothing is listed
+    if let Some(after) = after {
+        for elem in keys_iter.by_ref() {
+            if elem? == after {
+                break;
+            }
+        }
+    }
+
+    let mut list: Vec<PublicOffspringInfo> = vec![];
+    let mut next: Option<Addr> = None;
+    for elem in keys_iter {
+        let contract_addr = elem?;
+        if list.len() == limit {
+            next = list
+                .last()
+                .map(|offspring| offspring.contract.address.clone());
+            break;
+        }
+        let offspring_info = OFFSPRING_STORAGE
+            .get(deps.storage, &contract_addr)
+            .ok_or_else(|| ContractError::CustomError {
+                val: "Error occurred while loading offspring data".to_string(),
+            })?;
+        list.push(offspring_info.to_public_offspring_info());
+    }
+
+    Ok(to_binary(&QueryAnswer::ListMyOffspringFrom {
+        offspring: list,
+        next,
+        last_modified_height: load_last_modified_height(deps.storage)?,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> listing the page of the address' offspring, active ones
+/// first, whose label contains the search string
+///
+/// # Arguments
+///
+/// * `deps`           - Deps containing all the contract's external dependencies
+/// * `address`        - String address whose offspring are searched
+/// * `viewing_key`    - String key used to authenticate the query
+/// * `label_contains` - optional substring the label must contain
+/// * `filter`         - optional choice of active or inactive offspring. Defaults to all
+/// * `start_page`     - optional start page of the matches returned
+/// * `page_size`      - optional number of matches to return in this page
+fn try_search_my(
+    deps: Deps,
+    address: String,
+    viewing_key: String,
+    label_contains: Option<String>,
+    filter: Option<FilterTypes>,
+    start_page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<Binary, ContractError> {
+    let addr = deps.api.addr_validate(&address)?;
+    if !is_key_valid(deps.storage, addr.as_str(), viewing_key) {
+        return Err(ContractError::ViewingKeyOrUnauthorized {});
+    }
+    let types = filter.unwrap_or(FilterTypes::All);
+    let size = page_size_or_default(page_size) as usize;
+    let mut to_skip = (start_page.unwrap_or(0) as usize) * size;
+
+    let owners_active_store = OWNERS_ACTIVE.add_suffix(addr.to_string().as_bytes());
+    let owners_inactive_store = OWNERS_INACTIVE.add_suffix(addr.to_string().as_bytes());
+    let mut keysets: Vec<&Keyset<Addr>> = vec![];
+    if types == FilterTypes::Active || types == FilterTypes::All {
+        keysets.push(&owners_active_store);
+    }
+    if types == FilterTypes::Inactive || types == FilterTypes::All {
+        keysets.push(&owners_inactive_store);
+    }
+
+    let mut list: Vec<PublicOffspringInfo> = vec![];
+    let mut has_more = false;
+    'search: for keyset in keysets {
+        for elem in keyset.iter(deps.storage)? {
+            let contract_addr = elem?;
+            let offspring_info = match OFFSPRING_STORAGE.get(deps.storage, &contract_addr) {
+                Some(offspring_info) => offspring_info,
+                None => continue,
+            };
+            if let Some(pattern) = &label_contains {
+                if !offspring_info.label.contains(pattern.as_str()) {
+                    continue;
+                }
+            }
+            if to_skip > 0 {
+                to_skip -= 1;
+                continue;
+            }
+            if list.len() == size {
+                has_more = true;
+                break 'search;
+            }
+            list.push(offspring_info.to_public_offspring_info());
+        }
+    }
+
+    Ok(to_binary(&QueryAnswer::SearchMyOffspring {
+        offspring: list,
+        has_more,
+        last_modified_height: load_last_modified_height(deps.storage)?,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the last reported counts of the requested
+/// offspring that the address owns and that report their count
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `addresses`   - offspring addresses to look up
+/// * `address`     - String address whose offspring are looked up
+/// * `viewing_key` - String key used to authenticate the query
+fn try_batch_counts(
+    deps: Deps,
+    addresses: Vec<String>,
+    address: String,
+    viewing_key: String,
+) -> Result<Binary, ContractError> {
+    let addr = deps.api.addr_validate(&address)?;
+    if !is_key_valid(deps.storage, addr.as_str(), viewing_key) {
+        return Err(ContractError::ViewingKeyOrUnauthorized {});
+    }
+    if addresses.len() > MAX_COUNT_BATCH {
+        return Err(ContractError::BatchTooLarge {
+            max: MAX_COUNT_BATCH,
+        });
+    }
+    let mut counts: Vec<OffspringCount> = vec![];
+    for offspring in addresses {
+        let offspring_addr = deps.api.addr_validate(&offspring)?;
+        if let Some(offspring_info) = OFFSPRING_STORAGE.get(deps.storage, &offspring_addr) {
+            if offspring_info.owner == addr && offspring_info.reports_count {
+                counts.push(OffspringCount {
+                    address: offspring_addr,
+                    count: offspring_info.last_count,
+                });
+            }
+        }
+    }
+
+    Ok(to_binary(&QueryAnswer::BatchCounts { counts })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying the number of offspring the address owns
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `address`     - String address whose offspring are counted
+/// * `viewing_key` - String key used to authenticate the query
+fn try_owner_summary(
+    deps: Deps,
+    address: String,
+    viewing_key: String,
+) -> Result<Binary, ContractError> {
+    let addr = deps.api.addr_validate(&address)?;
+    if !is_key_valid(deps.storage, addr.as_str(), viewing_key) {
+        return Err(ContractError::ViewingKeyOrUnauthorized {});
+    }
+    let (active_count, inactive_count) = owner_counts(deps.storage, &addr)?;
+
+    Ok(to_binary(&QueryAnswer::OwnerSummary {
+        active_count,
+        inactive_count,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> displaying whether the address/key pair is valid and,
+/// if so, the number of offspring the address owns
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `address`     - String address whose offspring are counted
+/// * `viewing_key` - String key used to authenticate the query
+fn try_validate_and_count(
+    deps: Deps,
+    address: String,
+    viewing_key: String,
+) -> Result<Binary, ContractError> {
+    let is_valid = is_key_valid(deps.storage, &address, viewing_key);
+    let (active_count, inactive_count) = if is_valid {
+        owner_counts(deps.storage, &deps.api.addr_validate(&address)?)?
+    } else {
+        (0, 0)
+    };
+
+    Ok(to_binary(&QueryAnswer::ValidateAndCount {
+        is_valid,
+        active_count,
+        inactive_count,
+    })?)
+}
+
+/// Returns Result<(u32, u32), ContractError>, the number of active and inactive offspring the
+/// owner has
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `owner`   - a reference to the address of the owner
+fn owner_counts(storage: &dyn Storage, owner: &Addr) -> Result<(u32, u32), ContractError> {
+    let active_count = OWNERS_ACTIVE
+        .add_suffix(owner.to_string().as_bytes())
+        .get_len(storage)?;
+    let inactive_count = OWNERS_INACTIVE
+        .add_suffix(owner.to_string().as_bytes())
+        .get_len(storage)?;
+    Ok((active_count, inactive_count))
+}
+
+/// Returns Result<(Vec<PublicOffspringInfo>, bool, Vec<Addr>), ContractError>
+///
+/// provide the appropriate list of active/inactive offspring, whether there is another page, and
+/// the listed addresses whose offspring info is missing. Missing entries are skipped so that they
+/// do not break the whole page.
+///
+/// # Arguments
+///
+/// * `storage`    - a reference to the contract's storage
+/// * `owner`      - optional owner only whose offspring are listed. If none, then we list all active/inactive
+/// * `filter`     - Specify whether you want active or inactive offspring to be listed
+/// * `start_page` - optional start page for the offsprings returned and listed
+/// * `page_size`  - optional number of offspring to return in this page
+fn display_active_or_inactive_list(
+    storage: &dyn Storage,
+    owner: Option<Addr>,
+    filter: FilterTypes,
+    start_page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<(Vec<PublicOffspringInfo>, bool, Vec<Addr>), ContractError> {
+    let start_page = start_page.unwrap_or(0);
+    let size = page_size_or_default(page_size);
+    let mut list: Vec<PublicOffspringInfo> = vec![];
+
+    let keyset: &Keyset<Addr>;
+    let owners_active_store: Keyset<Addr>;
+    let owners_inactive_store: Keyset<Addr>;
+    match filter {
+        FilterTypes::Active => {
+            if let Some(owner_addr) = owner {
+                owners_active_store = OWNERS_ACTIVE.add_suffix(owner_addr.to_string().as_bytes());
+                keyset = &owners_active_store;
+            } else {
+                keyset = &ACTIVE_STORE;
+            }
+        }
+        FilterTypes::Inactive => {
+            if let Some(owner_addr) = owner {
+                owners_inactive_store =
+                    OWNERS_INACTIVE.add_suffix(owner_addr.to_string().as_bytes());
+                keyset = &owners_inactive_store;
+            } else {
+                keyset = &INACTIVE_STORE;
+            }
+        }
+        FilterTypes::All => {
+            return Err(ContractError::CustomError {
+                val: "Please select one of active or inactive offspring to list.".to_string(),
+            });
+        }
+    }
+
+    let mut paginated_keys_iter = keyset
+        .iter(storage)?
+        .skip((start_page as usize) * (size as usize))
+        // take one extra element to know whether there is another page
+        .take(size as usize + 1);
+    let mut has_more = false;
+    let mut orphans: Vec<Addr> = vec![];
+
+    loop {
+        let may_next_elem = paginated_keys_iter.next();
+        if let Some(elem) = may_next_elem {
+            if list.len() == size as usize {
+                has_more = true;
+                break;
+            }
+            let contract_addr = elem?;
+            match OFFSPRING_STORAGE.get(storage, &contract_addr) {
+                Some(offspring_info) => list.push(offspring_info.to_public_offspring_info()),
+                None => orphans.push(contract_addr),
+            }
+        } else {
+            break;
+        }
+    }
+
+    Ok((list, has_more, orphans))
+}
+
+/// Returns Result<Binary, ContractError> listing the inactive offspring
+///
+/// # Arguments
+///
+/// * `deps`       - Deps containing all the contract's external dependencies
+/// * `start_page` - optional start page for the offsprings returned and listed
+/// * `page_size`  - optional number of offspring to display
+fn try_list_inactive(
+    deps: Deps,
+    start_page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<Binary, ContractError> {
+    let start_page = start_page.unwrap_or(0);
+    let page_size = page_size_or_default(page_size);
+    let (items, has_more, orphans) = display_active_or_inactive_list(
+        deps.storage,
+        None,
+        FilterTypes::Inactive,
+        Some(start_page),
+        Some(page_size),
+    )?;
+    Ok(to_binary(&QueryAnswer::ListInactiveOffspring {
+        inactive: Page {
+            items,
+            start_page,
+            page_size,
+            total: INACTIVE_STORE.get_len(deps.storage)?,
+            has_more,
+        },
+        orphans,
+        last_modified_height: load_last_modified_height(deps.storage)?,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> listing all registered offspring
+///
+/// # Arguments
+///
+/// * `deps`       - Deps containing all the contract's external dependencies
+/// * `start_page` - optional start page for the offsprings returned and listed
+/// * `page_size`  - optional number of offspring to display
+fn try_list_all(
+    deps: Deps,
+    start_page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<Binary, ContractError> {
+    let start_page = start_page.unwrap_or(0);
+    let size = page_size_or_default(page_size);
+    let mut list: Vec<PublicOffspringInfo> = vec![];
+
+    let mut paginated_iter = OFFSPRING_STORAGE
+        .iter(deps.storage)?
+        .skip((start_page as usize) * (size as usize))
+        .take(size as usize);
+
+    loop {
+        let may_next_elem = paginated_iter.next();
+        if let Some(elem) = may_next_elem {
+            let (_, offspring_info) = elem?;
+            list.push(offspring_info.to_public_offspring_info());
+        } else {
+            break;
+        }
+    }
+
+    Ok(to_binary(&QueryAnswer::ListAllOffspring {
+        offspring: list,
+        last_modified_height: load_last_modified_height(deps.storage)?,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> listing all registered offspring for the admin. Unlike
+/// ListAllOffspring, pages can hold up to MAX_ADMIN_PAGE_SIZE offspring
+///
+/// # Arguments
+///
+/// * `deps`              - Deps containing all the contract's external dependencies
+/// * `admin_viewing_key` - String key used to authenticate the admin
+/// * `start_page`        - optional start page for the offsprings returned and listed
+/// * `page_size`         - optional number of offspring to display
+fn try_admin_list_all(
+    deps: Deps,
+    admin_viewing_key: String,
+    start_page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<Binary, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if !is_key_valid(deps.storage, admin.as_str(), admin_viewing_key) {
+        return Err(ContractError::ViewingKeyOrUnauthorized {});
+    }
+    let start_page = start_page.unwrap_or(0);
+    // the admin is not held to the public MAX_PAGE_SIZE
+    let size = match page_size {
+        None | Some(0) => DEFAULT_PAGE_SIZE,
+        Some(size) => size.min(MAX_ADMIN_PAGE_SIZE),
+    };
+    let total = OFFSPRING_STORAGE.get_len(deps.storage)?;
+    let mut list: Vec<StoreOffspringInfo> = vec![];
+
+    let mut paginated_iter = OFFSPRING_STORAGE
+        .iter(deps.storage)?
+        .skip((start_page as usize) * (size as usize))
+        .take(size as usize);
+
+    loop {
+        let may_next_elem = paginated_iter.next();
+        if let Some(elem) = may_next_elem {
+            let (_, offspring_info) = elem?;
+            list.push(offspring_info);
+        } else {
+            break;
+        }
+    }
+
+    Ok(to_binary(&QueryAnswer::AdminListAll {
+        offspring: list,
+        total,
+        has_more: (start_page as u64 + 1) * (size as u64) < total as u64,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> listing the address and label of every registered
+/// offspring
+///
+/// # Arguments
+///
+/// * `deps`       - Deps containing all the contract's external dependencies
+/// * `start_page` - optional start page for the labels returned and listed
+/// * `page_size`  - optional number of labels to display
+fn try_list_labels(
+    deps: Deps,
+    start_page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<Binary, ContractError> {
+    let start_page = start_page.unwrap_or(0);
+    let size = page_size_or_default(page_size);
+    let mut labels: Vec<(Addr, String)> = vec![];
+
+    let mut paginated_iter = OFFSPRING_STORAGE
+        .iter(deps.storage)?
+        .skip((start_page as usize) * (size as usize))
+        // take one extra element to know whether there is another page
+        .take(size as usize + 1);
+    let mut scanned: u32 = 0;
+    let mut has_more = false;
+
+    loop {
+        let may_next_elem = paginated_iter.next();
+        if let Some(elem) = may_next_elem {
+            if scanned == size {
+                has_more = true;
+                break;
+            }
+            scanned += 1;
+            let (address, offspring_info) = elem?;
+            labels.push((address, offspring_info.label));
+        } else {
+            break;
+        }
+    }
+
+    Ok(to_binary(&QueryAnswer::ListLabels { labels, has_more })?)
+}
+
+/// Returns Result<Binary, ContractError> listing the registered offspring created within a block
+/// height range. Pages are taken from all registered offspring before filtering, so a page may
+/// hold fewer than page_size offspring even if more pages follow
+///
+/// # Arguments
+///
+/// * `deps`       - Deps containing all the contract's external dependencies
+/// * `from`       - lowest block height of the range, inclusive
+/// * `to`         - highest block height of the range, inclusive
+/// * `start_page` - optional start page of the registered offspring scanned
+/// * `page_size`  - optional number of registered offspring to scan
+fn try_list_by_height_range(
+    deps: Deps,
+    from: u64,
+    to: u64,
     start_page: Option<u32>,
     page_size: Option<u32>,
 ) -> Result<Binary, ContractError> {
-    let addr;
-    // add permits
-    if let (Some(address), Some(viewing_key)) = (address, viewing_key) {
-        addr = deps.api.addr_validate(&address)?;
-        // if key matches
-        if !is_key_valid(deps.storage, addr.as_str(), viewing_key) {
-            return Ok(to_binary(&QueryAnswer::ViewingKeyError {
-                error: "Wrong viewing key for this address or viewing key not set".to_string(),
-            })?);
-        }
-    } else if let Some(permit) = permit {
-        addr = is_permit_valid(deps, &permit, env.contract.address.to_string())?;
-    } else {
-        return Err(ContractError::Unauthorized {});
+    if from > to {
+        return Err(ContractError::CustomError {
+            val: "The range must not start above the height it ends at".to_string(),
+        });
     }
-    let mut active_list: Option<Vec<StoreOffspringInfo>> = None;
-    let mut inactive_list: Option<Vec<StoreOffspringInfo>> = None;
-    // if no filter default to ALL
-    let types = filter.unwrap_or(FilterTypes::All);
+    let start_page = start_page.unwrap_or(0);
+    let size = page_size_or_default(page_size);
+    let mut list: Vec<PublicOffspringInfo> = vec![];
 
-    // list the active offspring
-    if types == FilterTypes::Active || types == FilterTypes::All {
-        active_list = Some(display_active_or_inactive_list(
-            deps.storage,
-            Some(addr.clone()),
-            FilterTypes::Active,
-            start_page,
-            page_size,
-        )?);
-    }
-    // list the inactive offspring
-    if types == FilterTypes::Inactive || types == FilterTypes::All {
-        inactive_list = Some(display_active_or_inactive_list(
-            deps.storage,
-            Some(addr),
-            FilterTypes::Inactive,
-            start_page,
-            page_size,
-        )?);
+    let mut paginated_iter = OFFSPRING_STORAGE
+        .iter(deps.storage)?
+        .skip((start_page as usize) * (size as usize))
+        // take one extra element to know whether there is another page
+        .take(size as usize + 1);
+    let mut scanned: u32 = 0;
+    let mut has_more = false;
+
+    loop {
+        let may_next_elem = paginated_iter.next();
+        if let Some(elem) = may_next_elem {
+            if scanned == size {
+                has_more = true;
+                break;
+            }
+            scanned += 1;
+            let (_, offspring_info) = elem?;
+            if (from..=to).contains(&offspring_info.created_height) {
+                list.push(offspring_info.to_public_offspring_info());
+            }
+        } else {
+            break;
+        }
     }
 
-    Ok(to_binary(&QueryAnswer::ListMyOffspring {
-        active: active_list,
-        inactive: inactive_list,
+    Ok(to_binary(&QueryAnswer::ListByHeightRange {
+        offspring: list,
+        has_more,
     })?)
 }
 
-/// Returns Result<Vec<StoreOffspringInfo>, ContractError>
-///
-/// provide the appropriate list of active/inactive offspring
+/// Returns Result<Binary, ContractError> listing the registered offspring created with a
+/// category. Pages are counted over all registered offspring, so a page may hold fewer than
+/// page_size offspring even if more pages follow
 ///
 /// # Arguments
 ///
-/// * `storage`    - a reference to the contract's storage
-/// * `owner`      - optional owner only whose offspring are listed. If none, then we list all active/inactive
-/// * `filter`     - Specify whether you want active or inactive offspring to be listed
-/// * `start_page` - optional start page for the offsprings returned and listed
-/// * `page_size`  - optional number of offspring to return in this page
-fn display_active_or_inactive_list(
-    storage: &dyn Storage,
-    owner: Option<Addr>,
-    filter: FilterTypes,
+/// * `deps`       - Deps containing all the contract's external dependencies
+/// * `category`   - category of the listed offspring
+/// * `start_page` - optional start page of the registered offspring scanned
+/// * `page_size`  - optional number of registered offspring to scan
+fn try_list_by_category(
+    deps: Deps,
+    category: String,
     start_page: Option<u32>,
     page_size: Option<u32>,
-) -> Result<Vec<StoreOffspringInfo>, ContractError> {
+) -> Result<Binary, ContractError> {
     let start_page = start_page.unwrap_or(0);
-    let size = page_size.unwrap_or(DEFAULT_PAGE_SIZE);
-    let mut list: Vec<StoreOffspringInfo> = vec![];
+    let size = page_size_or_default(page_size);
+    let mut list: Vec<PublicOffspringInfo> = vec![];
 
-    let keyset: &Keyset<Addr>;
-    let owners_active_store: Keyset<Addr>;
-    let owners_inactive_store: Keyset<Addr>;
-    match filter {
-        FilterTypes::Active => {
-            if let Some(owner_addr) = owner {
-                owners_active_store = OWNERS_ACTIVE.add_suffix(owner_addr.to_string().as_bytes());
-                keyset = &owners_active_store;
-            } else {
-                keyset = &ACTIVE_STORE;
+    let mut paginated_iter = OFFSPRING_STORAGE
+        .iter(deps.storage)?
+        .skip((start_page as usize) * (size as usize))
+        // take one extra element to know whether there is another page
+        .take(size as usize + 1);
+    let mut scanned: u32 = 0;
+    let mut has_more = false;
+
+    loop {
+        let may_next_elem = paginated_iter.next();
+        if let Some(elem) = may_next_elem {
+            if scanned == size {
+                has_more = true;
+                break;
             }
-        }
-        FilterTypes::Inactive => {
-            if let Some(owner_addr) = owner {
-                owners_inactive_store =
-                    OWNERS_INACTIVE.add_suffix(owner_addr.to_string().as_bytes());
-                keyset = &owners_inactive_store;
-            } else {
-                keyset = &INACTIVE_STORE;
+            scanned += 1;
+            let (_, offspring_info) = elem?;
+            if offspring_info.category.as_ref() == Some(&category) {
+                list.push(offspring_info.to_public_offspring_info());
             }
-        }
-        FilterTypes::All => {
-            return Err(ContractError::CustomError {
-                val: "Please select one of active or inactive offspring to list.".to_string(),
-            });
+        } else {
+            break;
         }
     }
 
-    let mut paginated_keys_iter = keyset
-        .iter(storage)?
+    Ok(to_binary(&QueryAnswer::ListByCategory {
+        offspring: list,
+        has_more,
+    })?)
+}
+
+/// Returns Result<Binary, ContractError> listing every category offspring were created with
+///
+/// # Arguments
+///
+/// * `deps`       - Deps containing all the contract's external dependencies
+/// * `start_page` - optional start page for the categories returned and listed
+/// * `page_size`  - optional number of categories to display
+fn try_list_categories(
+    deps: Deps,
+    start_page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<Binary, ContractError> {
+    let start_page = start_page.unwrap_or(0);
+    let size = page_size_or_default(page_size);
+    let mut categories: Vec<String> = vec![];
+
+    let mut paginated_keys_iter = CATEGORIES
+        .iter_keys(deps.storage)?
         .skip((start_page as usize) * (size as usize))
         .take(size as usize);
 
     loop {
         let may_next_elem = paginated_keys_iter.next();
         if let Some(elem) = may_next_elem {
-            let contract_addr = elem?;
-            let offspring_info =
-                OFFSPRING_STORAGE
-                    .get(storage, &contract_addr)
-                    .ok_or_else(|| ContractError::CustomError {
-                        val: "Error occurred while loading offspring data".to_string(),
-                    })?;
-            list.push(offspring_info);
+            categories.push(elem?);
         } else {
             break;
         }
     }
 
-    Ok(list)
+    Ok(to_binary(&QueryAnswer::ListCategories { categories })?)
 }
 
-/// Returns Result<Binary, ContractError> listing the inactive offspring
+/// Returns Result<Binary, ContractError> listing the offspring whose owners renounced ownership
 ///
 /// # Arguments
 ///
 /// * `deps`       - Deps containing all the contract's external dependencies
 /// * `start_page` - optional start page for the offsprings returned and listed
 /// * `page_size`  - optional number of offspring to display
-fn try_list_inactive(
+fn try_list_ownerless(
     deps: Deps,
     start_page: Option<u32>,
     page_size: Option<u32>,
 ) -> Result<Binary, ContractError> {
-    Ok(to_binary(&QueryAnswer::ListInactiveOffspring {
-        inactive: display_active_or_inactive_list(
-            deps.storage,
+    let start_page = start_page.unwrap_or(0);
+    let size = page_size_or_default(page_size);
+    let mut offspring: Vec<Addr> = vec![];
+
+    let mut paginated_iter = OWNERLESS
+        .iter(deps.storage)?
+        .skip((start_page as usize) * (size as usize))
+        .take(size as usize);
+
+    loop {
+        let may_next_elem = paginated_iter.next();
+        if let Some(elem) = may_next_elem {
+            offspring.push(elem?);
+        } else {
+            break;
+        }
+    }
+
+    Ok(to_binary(&QueryAnswer::ListOwnerless { offspring })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{OwnedDeps, SubMsgResponse};
+
+    const CODE_HASH: &str = "d519793af2623773f46967192c9afcd9f2e3a2ba0fd927ea6bf3448a723bde6b";
+
+    fn init_msg() -> InstantiateMsg {
+        InstantiateMsg {
+            offspring_code_info: CodeInfo {
+                code_id: 2,
+                code_hash: CODE_HASH.to_string(),
+            },
+            min_initial_count: None,
+            max_initial_count: None,
+            allow_negative_count: None,
+            require_activation: false,
+            max_offspring: None,
+            max_failed_registrations: None,
+            block_size: None,
+            name: None,
+        }
+    }
+
+    fn setup(msg: InstantiateMsg) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+        deps
+    }
+
+    fn reply_info(offspring: &str, owner: &str) -> ReplyOffspringInfo {
+        ReplyOffspringInfo {
+            label: format!("{}_label", offspring),
+            title: None,
+            owner: Addr::unchecked(owner),
+            address: Addr::unchecked(offspring),
+            code_hash: CODE_HASH.to_string(),
+            count: 0,
+            report_count: false,
+            correlation_id: None,
+        }
+    }
+
+    fn register(deps: DepsMut, offspring: &str, owner: &str) {
+        register_offspring_impl(
+            deps,
+            mock_env(),
+            reply_info(offspring, owner),
+            2,
             None,
-            FilterTypes::Inactive,
-            start_page,
-            page_size,
-        )?,
-    })?)
+            None,
+        )
+        .unwrap();
+    }
+
+    fn owner_lists(deps: Deps, owner: &str) -> (Vec<Addr>, Vec<Addr>) {
+        let active = OWNERS_ACTIVE
+            .add_suffix(owner.as_bytes())
+            .iter(deps.storage)
+            .unwrap()
+            .collect::<StdResult<Vec<Addr>>>()
+            .unwrap();
+        let inactive = OWNERS_INACTIVE
+            .add_suffix(owner.as_bytes())
+            .iter(deps.storage)
+            .unwrap()
+            .collect::<StdResult<Vec<Addr>>>()
+            .unwrap();
+        (active, inactive)
+    }
+
+    fn create_msg(label: &str, owner: &str) -> ExecuteMsg {
+        ExecuteMsg::CreateOffspring {
+            label: label.to_string(),
+            title: None,
+            owner: owner.to_string(),
+            count: 0,
+            description: None,
+            owner_only_increment: false,
+            allowed_parity: None,
+            local_permit_validation: false,
+            respect_factory_policy: false,
+            correlation_id: None,
+            auto_create_key: None,
+            code_id: None,
+            category: None,
+            report_count: false,
+        }
+    }
+
+    fn success_reply(offspring: &str, owner: &str) -> Reply {
+        let data = format!(
+            r#"{{"label":"{}_label","owner":"{}","address":"{}","code_hash":"{}"}}"#,
+            offspring, owner, offspring, CODE_HASH
+        );
+        Reply {
+            id: OFFSPRING_INSTANTIATE_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(Binary::from(data.as_bytes())),
+            }),
+        }
+    }
+
+    fn failed_reply() -> Reply {
+        Reply {
+            id: OFFSPRING_INSTANTIATE_REPLY_ID,
+            result: SubMsgResult::Err("instantiation failed".to_string()),
+        }
+    }
+
+    #[test]
+    fn invalid_code_info_is_rejected() {
+        let invalid = [
+            CodeInfo {
+                code_id: 2,
+                code_hash: String::new(),
+            },
+            CodeInfo {
+                code_id: 0,
+                code_hash: CODE_HASH.to_string(),
+            },
+        ];
+        for offspring_code_info in invalid {
+            let mut deps = mock_dependencies();
+            let msg = InstantiateMsg {
+                offspring_code_info,
+                ..init_msg()
+            };
+            let err = instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg);
+            assert!(matches!(err, Err(ContractError::InvalidCodeInfo { .. })));
+        }
+
+        let mut deps = setup(init_msg());
+        let invalid = [
+            CodeInfo {
+                code_id: 3,
+                code_hash: String::new(),
+            },
+            CodeInfo {
+                code_id: 0,
+                code_hash: CODE_HASH.to_string(),
+            },
+        ];
+        for offspring_code_info in invalid {
+            let msg = ExecuteMsg::NewOffspringContract {
+                offspring_code_info,
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg);
+            assert!(matches!(err, Err(ContractError::InvalidCodeInfo { .. })));
+        }
+        assert_eq!(OFFSPRING_CODE.load(&deps.storage).unwrap().code_id, 2);
+    }
+
+    #[test]
+    fn deactivate_uses_recorded_owner() {
+        let mut deps = setup(init_msg());
+        register(deps.as_mut(), "offspring", "owner");
+
+        let msg = ExecuteMsg::DeactivateOffspring { final_count: None };
+        execute(deps.as_mut(), mock_env(), mock_info("offspring", &[]), msg).unwrap();
+
+        let offspring = Addr::unchecked("offspring");
+        assert_eq!(
+            owner_lists(deps.as_ref(), "owner"),
+            (vec![], vec![offspring.clone()])
+        );
+        assert!(INACTIVE_STORE.contains(&deps.storage, &offspring));
+        assert!(!ACTIVE_STORE.contains(&deps.storage, &offspring));
+    }
+
+    #[test]
+    fn label_length_boundary() {
+        let mut deps = setup(init_msg());
+        let msg = create_msg(&"l".repeat(MAX_LABEL_LEN), "owner");
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        let msg = create_msg(&"l".repeat(MAX_LABEL_LEN + 1), "owner");
+        let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert!(matches!(err, Err(ContractError::LabelTooLong { .. })));
+    }
+
+    #[test]
+    fn negative_count_is_rejected() {
+        let mut deps = setup(InstantiateMsg {
+            allow_negative_count: Some(false),
+            ..init_msg()
+        });
+        let msg = create_msg("counter", "owner");
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let mut msg = create_msg("negative", "owner");
+        if let ExecuteMsg::CreateOffspring { count, .. } = &mut msg {
+            *count = -1;
+        }
+        let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert!(matches!(
+            err,
+            Err(ContractError::NegativeCount { count: -1 })
+        ));
+    }
+
+    #[test]
+    fn missing_info_is_listed_as_orphan() {
+        let mut deps = setup(init_msg());
+        register(deps.as_mut(), "offspring1", "owner");
+        register(deps.as_mut(), "orphan", "owner");
+        OFFSPRING_STORAGE
+            .remove(&mut deps.storage, &Addr::unchecked("orphan"))
+            .unwrap();
+
+        let msg = QueryMsg::ListActiveOffspring {
+            start_page: None,
+            page_size: None,
+        };
+        let answer: QueryAnswer =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        match answer {
+            QueryAnswer::ListActiveOffspring {
+                active, orphans, ..
+            } => {
+                assert_eq!(active.items.len(), 1);
+                assert_eq!(orphans, vec![Addr::unchecked("orphan")]);
+            }
+            _ => panic!("unexpected answer"),
+        }
+    }
+
+    #[test]
+    fn stored_info_without_title_loads() {
+        let mut deps = setup(init_msg());
+        let info = reply_info("offspring", "owner").to_store_offspring_info(
+            2,
+            mock_env().block.height,
+            Some("games".to_string()),
+        );
+        OFFSPRING_STORAGE
+            .insert(&mut deps.storage, &Addr::unchecked("offspring"), &info)
+            .unwrap();
+
+        let loaded = OFFSPRING_STORAGE
+            .get(&deps.storage, &Addr::unchecked("offspring"))
+            .unwrap();
+        assert!(loaded.title.is_none());
+        assert_eq!(loaded.category, Some("games".to_string()));
+    }
+
+    #[test]
+    fn auto_created_key_is_valid() {
+        let mut deps = setup(init_msg());
+        let mut msg = create_msg("counter", "owner");
+        if let ExecuteMsg::CreateOffspring {
+            auto_create_key, ..
+        } = &mut msg
+        {
+            *auto_create_key = Some("entropy123".to_string());
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            success_reply("offspring", "owner"),
+        )
+        .unwrap();
+
+        let viewing_key = match from_binary(&res.data.unwrap()).unwrap() {
+            HandleAnswer::OffspringCreated {
+                viewing_key: Some(viewing_key),
+                ..
+            } => viewing_key,
+            _ => panic!("no viewing key was created"),
+        };
+        let msg = QueryMsg::IsKeyValid {
+            address: "owner".to_string(),
+            viewing_key,
+        };
+        let answer: QueryAnswer =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert!(matches!(
+            answer,
+            QueryAnswer::IsKeyValid { is_valid: true, .. }
+        ));
+    }
+
+    #[test]
+    fn unknown_offspring_is_not_found() {
+        let mut deps = setup(init_msg());
+        let not_found = |res: Result<_, ContractError>| {
+            matches!(res, Err(ContractError::OffspringNotFound { .. }))
+        };
+
+        let msg = ExecuteMsg::DeactivateOffspring { final_count: None };
+        assert!(not_found(execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("unknown", &[]),
+            msg
+        )));
+        let msg = ExecuteMsg::PurgeOffspring {};
+        assert!(not_found(execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("unknown", &[]),
+            msg
+        )));
+        for msg in [
+            ExecuteMsg::ActivateOffspring {
+                offspring: "unknown".to_string(),
+            },
+            ExecuteMsg::OwnerDeactivate {
+                offspring: "unknown".to_string(),
+            },
+            ExecuteMsg::OwnerReactivate {
+                offspring: "unknown".to_string(),
+            },
+        ] {
+            assert!(not_found(execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                msg
+            )));
+        }
+
+        let msg = QueryMsg::GetMyInfo {
+            offspring: "unknown".to_string(),
+        };
+        let err = query(deps.as_ref(), mock_env(), msg);
+        assert!(matches!(err, Err(ContractError::OffspringNotFound { .. })));
+    }
+
+    #[test]
+    fn stored_info_without_category_loads() {
+        let mut deps = setup(init_msg());
+        register(deps.as_mut(), "offspring", "owner");
+
+        let loaded = OFFSPRING_STORAGE
+            .get(&deps.storage, &Addr::unchecked("offspring"))
+            .unwrap();
+        assert!(loaded.category.is_none());
+        assert_eq!(loaded.owner, Addr::unchecked("owner"));
+    }
+
+    #[test]
+    fn public_answers_do_not_reveal_owner() {
+        let mut deps = setup(init_msg());
+        register(deps.as_mut(), "offspring", "owner");
+
+        let msg = QueryMsg::ListActiveOffspring {
+            start_page: None,
+            page_size: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        assert!(!String::from_utf8(res.0).unwrap().contains("owner"));
+
+        let msg = QueryMsg::GetMyInfo {
+            offspring: "offspring".to_string(),
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        assert!(!String::from_utf8(res.0).unwrap().contains("owner"));
+    }
+
+    #[test]
+    fn only_opted_in_counts_are_shown() {
+        let mut deps = setup(init_msg());
+        register(deps.as_mut(), "private", "owner");
+        let mut reporting = reply_info("reporting", "owner");
+        reporting.count = 7;
+        reporting.report_count = true;
+        register_offspring_impl(deps.as_mut(), mock_env(), reporting, 2, None, None).unwrap();
+
+        let msg = ExecuteMsg::ReportCount { count: 5 };
+        execute(deps.as_mut(), mock_env(), mock_info("private", &[]), msg).unwrap_err();
+        let msg = ExecuteMsg::ReportCount { count: 9 };
+        execute(deps.as_mut(), mock_env(), mock_info("reporting", &[]), msg).unwrap();
+
+        let msg = QueryMsg::TopOffspring { limit: None };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        match from_binary(&res).unwrap() {
+            QueryAnswer::TopOffspring { offspring } => {
+                assert_eq!(offspring.len(), 1);
+                assert_eq!(offspring[0].address, Addr::unchecked("reporting"));
+                assert_eq!(offspring[0].count, 9);
+            }
+            _ => panic!("unexpected query answer"),
+        }
+
+        let msg = QueryMsg::ListAllOffspring {
+            start_page: None,
+            page_size: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        assert!(!String::from_utf8(res.0).unwrap().contains("last_count"));
+    }
+
+    #[test]
+    fn failed_registration_keeps_owner_key() {
+        let mut deps = setup(InstantiateMsg {
+            max_failed_registrations: Some(5),
+            ..init_msg()
+        });
+        let msg = ExecuteMsg::SetViewingKey {
+            key: "old_key".to_string(),
+            padding: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let mut msg = create_msg("counter", "owner");
+        if let ExecuteMsg::CreateOffspring {
+            auto_create_key, ..
+        } = &mut msg
+        {
+            *auto_create_key = Some("entropy123".to_string());
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        reply(deps.as_mut(), mock_env(), failed_reply()).unwrap();
+
+        assert!(ViewingKey::check(&deps.storage, "owner", "old_key").is_ok());
+    }
+
+    #[test]
+    fn rejected_registration_is_not_tolerated() {
+        let mut deps = setup(InstantiateMsg {
+            max_failed_registrations: Some(0),
+            ..init_msg()
+        });
+        register(deps.as_mut(), "offspring", "owner");
+        let msg = create_msg("counter", "owner");
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        // an offspring that is already registered reverts its creation instead of being counted
+        let err = reply(
+            deps.as_mut(),
+            mock_env(),
+            success_reply("offspring", "owner"),
+        );
+
+        assert!(matches!(err, Err(ContractError::AlreadyRegistered { .. })));
+        assert_eq!(FAILED_REGISTRATIONS.load(&deps.storage).unwrap(), 0);
+        assert!(!IS_STOPPED.load(&deps.storage).unwrap());
+    }
+
+    #[test]
+    fn rebuild_skips_renounced_offspring() {
+        let mut deps = setup(init_msg());
+        register(deps.as_mut(), "offspring", "owner");
+        let msg = ExecuteMsg::RenounceOffspring {};
+        execute(deps.as_mut(), mock_env(), mock_info("offspring", &[]), msg).unwrap();
+
+        // a renounced offspring is recorded as owned by itself
+        let msg = ExecuteMsg::RebuildOwnerIndex {
+            owner: "offspring".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        assert_eq!(owner_lists(deps.as_ref(), "offspring"), (vec![], vec![]));
+        assert!(!OWNERS_SET.contains(&deps.storage, &Addr::unchecked("offspring")));
+    }
+
+    #[test]
+    fn offspring_info_only_tells_registration() {
+        let mut deps = setup(init_msg());
+        register(deps.as_mut(), "offspring", "owner");
+
+        for (offspring, registered) in [("offspring", true), ("unknown", false)] {
+            let msg = QueryMsg::OffspringInfo {
+                offspring: offspring.to_string(),
+            };
+            let answer: QueryAnswer =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            match answer {
+                QueryAnswer::OffspringInfo {
+                    is_registered,
+                    is_active,
+                } => {
+                    assert_eq!(is_registered, registered);
+                    assert_eq!(is_active, registered);
+                }
+                _ => panic!("unexpected answer"),
+            }
+        }
+    }
+
+    #[test]
+    fn page_sizes_are_capped() {
+        assert_eq!(page_size_or_default(None), DEFAULT_PAGE_SIZE);
+        assert_eq!(page_size_or_default(Some(0)), DEFAULT_PAGE_SIZE);
+        assert_eq!(page_size_or_default(Some(MAX_PAGE_SIZE)), MAX_PAGE_SIZE);
+        assert_eq!(page_size_or_default(Some(MAX_PAGE_SIZE + 1)), MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn list_from_missing_cursor_lists_nothing() {
+        let mut deps = setup(init_msg());
+        register(deps.as_mut(), "offspring", "owner");
+        let msg = ExecuteMsg::SetViewingKey {
+            key: "key".to_string(),
+            padding: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let msg = QueryMsg::ListMyOffspringFrom {
+            address: "owner".to_string(),
+            viewing_key: "key".to_string(),
+            after: Some("unknown".to_string()),
+            limit: None,
+        };
+        let answer: QueryAnswer =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        match answer {
+            QueryAnswer::ListMyOffspringFrom {
+                offspring, next, ..
+            } => {
+                assert!(offspring.is_empty());
+                assert!(next.is_none());
+            }
+            _ => panic!("unexpected answer"),
+        }
+    }
+
+    #[test]
+    fn activation_unfreezes_offspring() {
+        let mut deps = setup(InstantiateMsg {
+            require_activation: true,
+            ..init_msg()
+        });
+        register(deps.as_mut(), "offspring", "owner");
+        assert_eq!(
+            owner_lists(deps.as_ref(), "owner"),
+            (vec![], vec![Addr::unchecked("offspring")])
+        );
+
+        let msg = ExecuteMsg::ActivateOffspring {
+            offspring: "offspring".to_string(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(
+            owner_lists(deps.as_ref(), "owner"),
+            (vec![Addr::unchecked("offspring")], vec![])
+        );
+    }
+
+    #[test]
+    fn list_for_owners_tells_has_more() {
+        let mut deps = setup(init_msg());
+        for i in 0..=DEFAULT_PAGE_SIZE {
+            register(deps.as_mut(), &format!("offspring{}", i), "owner");
+        }
+        let msg = ExecuteMsg::SetViewingKey {
+            key: "admin_key".to_string(),
+            padding: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        let msg = QueryMsg::ListForOwners {
+            owners: vec!["owner".to_string()],
+            filter: None,
+            admin_viewing_key: "admin_key".to_string(),
+        };
+        let answer: QueryAnswer =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        match answer {
+            QueryAnswer::ListForOwners { owners } => {
+                assert_eq!(owners[0].active_has_more, Some(true));
+                assert_eq!(owners[0].inactive_has_more, Some(false));
+            }
+            _ => panic!("unexpected answer"),
+        }
+    }
+
+    #[test]
+    fn remove_offspring_code() {
+        let mut deps = setup(init_msg());
+        let msg = ExecuteMsg::NewOffspringContract {
+            offspring_code_info: CodeInfo {
+                code_id: 3,
+                code_hash: CODE_HASH.to_string(),
+            },
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        let remove = |code_id| ExecuteMsg::RemoveOffspringCode { code_id };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mallory", &[]),
+            remove(2),
+        );
+        assert!(matches!(err, Err(ContractError::Unauthorized {})));
+        // the current version stays
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            remove(3),
+        );
+        assert!(matches!(err, Err(ContractError::CustomError { .. })));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            remove(2),
+        )
+        .unwrap();
+        assert!(!OFFSPRING_CODES.contains(&deps.storage, &2));
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            remove(2),
+        );
+        assert!(matches!(
+            err,
+            Err(ContractError::UnknownCodeId { code_id: 2 })
+        ));
+    }
+
+    #[test]
+    fn list_labels_tells_has_more() {
+        let mut deps = setup(init_msg());
+        register(deps.as_mut(), "offspring1", "owner");
+        register(deps.as_mut(), "offspring2", "owner");
+
+        for (page_size, more) in [(1, true), (2, false)] {
+            let msg = QueryMsg::ListLabels {
+                start_page: None,
+                page_size: Some(page_size),
+            };
+            let answer: QueryAnswer =
+                from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            match answer {
+                QueryAnswer::ListLabels { labels, has_more } => {
+                    assert_eq!(labels.len(), page_size as usize);
+                    assert_eq!(has_more, more);
+                }
+                _ => panic!("unexpected answer"),
+            }
+        }
+    }
 }
@@ -9,12 +9,67 @@ pub enum ContractError {
     #[error("This contract is stopped")]
     Stopped {},
 
+    #[error("Offspring callbacks are frozen for maintenance, retry later")]
+    CallbacksFrozen {},
+
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Label can be at most {max} bytes long")]
+    LabelTooLong { max: usize },
+
+    #[error("Description can be at most {max} bytes long")]
+    DescriptionTooLong { max: usize },
+
+    #[error("Title can be at most {max} bytes long")]
+    TitleTooLong { max: usize },
+
+    #[error("Category can be at most {max} bytes long")]
+    CategoryTooLong { max: usize },
+
+    #[error("Name can be at most {max} bytes long")]
+    NameTooLong { max: usize },
+
     #[error("This address is unauthorized and/or viewing key is not valid")]
     ViewingKeyOrUnauthorized {},
 
+    #[error("Entropy must be at least {min} bytes long")]
+    InsufficientEntropy { min: usize },
+
+    #[error("Initial count {count} is out of bounds (min: {min:?}, max: {max:?})")]
+    CountOutOfBounds {
+        count: i32,
+        min: Option<i32>,
+        max: Option<i32>,
+    },
+
+    #[error("Initial count {count} cannot be negative")]
+    NegativeCount { count: i32 },
+
+    #[error("Minimum initial count cannot be greater than maximum initial count")]
+    InvalidCountBounds {},
+
+    #[error("Factory at capacity: it can create at most {max} offspring")]
+    AtCapacity { max: u64 },
+
+    #[error("Block size must be greater than zero")]
+    InvalidBlockSize {},
+
+    #[error("Offspring {address} is not registered")]
+    OffspringNotFound { address: String },
+
+    #[error("Offspring {address} is already registered")]
+    AlreadyRegistered { address: String },
+
+    #[error("At most {max} entries can be processed in one batch")]
+    BatchTooLarge { max: usize },
+
+    #[error("Offspring code id {code_id} is not a version of this factory")]
+    UnknownCodeId { code_id: u64 },
+
+    #[error("Invalid offspring code info: {msg}")]
+    InvalidCodeInfo { msg: String },
+
     #[error("Submessage (id: {id:?}) reply cannot be parsed.")]
     ParseReplyError { id: u64 },
 
@@ -4,16 +4,56 @@ use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::Addr;
 
-use crate::structs::{CodeInfo, StoreOffspringInfo};
+use crate::structs::{
+    Capabilities, CodeInfo, ContractInfo, CountBounds, OffspringCount, OperatorPermissions,
+    OwnerOffspring, Parity, PublicOffspringInfo, StoreOffspringInfo,
+};
 
 /// Instantiation message
 #[derive(Serialize, Deserialize, JsonSchema)]
 pub struct InstantiateMsg {
     /// offspring code info
     pub offspring_code_info: CodeInfo,
+    /// optional minimum initial count of new offspring (inclusive)
+    #[serde(default)]
+    pub min_initial_count: Option<i32>,
+    /// optional maximum initial count of new offspring (inclusive)
+    #[serde(default)]
+    pub max_initial_count: Option<i32>,
+    /// whether new offspring can start with a negative count. Default: true
+    #[serde(default)]
+    pub allow_negative_count: Option<bool>,
+    /// whether new offspring are listed as, and start, inactive until their owner activates them.
+    /// Default: false
+    #[serde(default)]
+    pub require_activation: bool,
+    /// optional maximum number of offspring the factory can ever create. No cap if omitted
+    #[serde(default)]
+    pub max_offspring: Option<u64>,
+    /// optional number of failed offspring instantiations to tolerate before the factory stops
+    /// itself. If omitted, a failed instantiation fails the CreateOffspring transaction instead of
+    /// being counted. A registration the factory rejects always fails the transaction
+    #[serde(default)]
+    pub max_failed_registrations: Option<u32>,
+    /// optional block size that responses are padded to. Default: BLOCK_SIZE (256)
+    #[serde(default)]
+    pub block_size: Option<usize>,
+    /// optional short name that identifies the factory in registries. At most MAX_NAME_LEN (64)
+    /// bytes long
+    #[serde(default)]
+    pub name: Option<String>,
 }
 
 /// Handle messages
+///
+/// Every handle (or reply) that registers an offspring or changes its status logs the same
+/// attributes so that a single parser can index them:
+/// * `action`    - `register_offspring`, `activate_offspring`, `deactivate_offspring`,
+///   `reactivate_offspring`, `report_count` or `purge_offspring`
+/// * `offspring` - address of the offspring
+/// * `owner`     - address of the offspring's owner
+/// * `code_id`   - code id the offspring was instantiated with
+/// * `height`    - block height the action happened at
 #[derive(Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -21,6 +61,10 @@ pub enum ExecuteMsg {
     CreateOffspring {
         /// String used to label when instantiating offspring contract.
         label: String,
+        /// optional display name of the offspring. Unlike the label, it does not have to be unique
+        /// and the owner can change it later
+        #[serde(default)]
+        title: Option<String>,
         //  the rest are meant to be contract specific data
         /// address of the owner associated to this offspring contract
         owner: String,
@@ -28,19 +72,121 @@ pub enum ExecuteMsg {
         count: i32,
         #[serde(default)]
         description: Option<String>,
+        /// whether only the owner can increment the counter. Default: false
+        #[serde(default)]
+        owner_only_increment: bool,
+        /// optional parity every count of the offspring must have, including the initial count.
+        /// Increments then step by 2 so that the count keeps it
+        #[serde(default)]
+        allowed_parity: Option<Parity>,
+        /// whether the offspring validates query permits itself instead of asking the factory.
+        /// Permits must then be signed for the offspring's address, and permits revoked in the
+        /// factory are still accepted by the offspring. They are revoked with the offspring's
+        /// RevokePermit instead. Default: false
+        #[serde(default)]
+        local_permit_validation: bool,
+        /// whether the offspring only lets its owner increment the counter while the factory
+        /// disallows public increments. Default: false
+        #[serde(default)]
+        respect_factory_policy: bool,
+        /// optional id logged as `correlation_id` when the offspring is registered, so that the
+        /// request can be tied to the registration
+        #[serde(default)]
+        correlation_id: Option<String>,
+        /// optional entropy used to also create a viewing key for the owner, returned in the
+        /// response. The key authenticates the owner to the offspring's queries like any key set
+        /// in the factory. The sender must be the owner. Must be at least MIN_ENTROPY_LENGTH (8)
+        /// bytes
+        #[serde(default)]
+        auto_create_key: Option<String>,
+        /// optional code id of the offspring version to instantiate. It must be a version the
+        /// factory was set to. Default: the current offspring version
+        #[serde(default)]
+        code_id: Option<u64>,
+        /// optional category to group the offspring by. At most MAX_CATEGORY_LEN (64) bytes
+        #[serde(default)]
+        category: Option<String>,
+        /// whether the offspring reports its count to the factory. Reported counts are shown to
+        /// anyone by TopOffspring and TotalCount. Default: false
+        #[serde(default)]
+        report_count: bool,
     },
 
     /// DeactivateOffspring tells the factory that the offspring is inactive.
     DeactivateOffspring {
-        /// offspring's owner
-        owner: Addr,
+        /// offspring's count when it was deactivated. Recorded as its last count if provided by an
+        /// offspring that reports its count
+        #[serde(default)]
+        final_count: Option<i32>,
+    },
+
+    /// ActivateOffspring lets an owner list a new offspring as active when the factory requires
+    /// activation
+    ActivateOffspring {
+        /// address of the offspring
+        offspring: String,
+    },
+
+    /// OwnerDeactivate lets an owner deactivate one of their active offspring from the factory
+    OwnerDeactivate {
+        /// address of the offspring
+        offspring: String,
+    },
+
+    /// OwnerReactivate lets an owner reactivate one of their inactive offspring from the factory
+    OwnerReactivate {
+        /// address of the offspring
+        offspring: String,
+    },
+
+    /// PurgeOffspring tells the factory to remove an inactive offspring from its records.
+    PurgeOffspring {},
+
+    /// TransferOffspring tells the factory the offspring has a new owner, so it is moved to the new
+    /// owner's lists
+    TransferOffspring {
+        /// offspring's new owner
+        new_owner: Addr,
+    },
+
+    /// RenounceOffspring tells the factory the offspring's owner renounced ownership, so it is
+    /// moved from the owner's lists to the ownerless offspring
+    RenounceOffspring {},
+
+    /// ReportCount tells the factory the offspring's current count. Only offspring created with
+    /// report_count can report it, and reported counts are publicly displayed by the factory.
+    ReportCount { count: i32 },
+
+    /// Allows the admin to register an offspring that exists on chain but failed to register
+    ManualRegister {
+        /// address of the offspring
+        offspring: String,
+        /// owner of the offspring
+        owner: String,
+        /// label the offspring was instantiated with
+        label: String,
+        /// code hash of the offspring
+        code_hash: String,
     },
 
-    /// Allows the admin to add a new offspring contract version
+    /// Allows the admin or an operator with can_set_code to add a new offspring contract version
     NewOffspringContract { offspring_code_info: CodeInfo },
 
+    /// Allows the admin or an operator with can_set_code to remove an offspring contract version,
+    /// so that CreateOffspring can no longer instantiate it. The current version can not be removed
+    RemoveOffspringCode {
+        /// code id of the version to remove
+        code_id: u64,
+    },
+
     /// Create a viewing key to be used with all factory and offspring authenticated queries
-    CreateViewingKey { entropy: String },
+    CreateViewingKey {
+        /// entropy used to create the viewing key. Must be at least MIN_ENTROPY_LENGTH (8) bytes
+        entropy: String,
+        // optional padding is mixed into the entropy and can be used so message length doesn't
+        // betray entropy length
+        padding: Option<String>,
+    },
 
     /// Set a viewing key to be used with all factory and offspring authenticated queries
     SetViewingKey {
@@ -49,9 +195,63 @@ pub enum ExecuteMsg {
         padding: Option<String>,
     },
 
-    /// Allows an admin to start/stop all offspring creation
+    /// Allows an admin or an operator with can_pause to start/stop all offspring creation
     SetStatus { stop: bool },
 
+    /// Allows the admin to change the bounds of the initial count of new offspring
+    SetCountBounds {
+        /// minimum initial count (inclusive). No minimum if omitted
+        #[serde(default)]
+        min_initial_count: Option<i32>,
+        /// maximum initial count (inclusive). No maximum if omitted
+        #[serde(default)]
+        max_initial_count: Option<i32>,
+    },
+
+    /// Allows the admin to change the maximum number of offspring the factory can ever create
+    SetMaxOffspring {
+        /// new cap. Removes the cap if omitted
+        #[serde(default)]
+        max_offspring: Option<u64>,
+    },
+
+    /// Allows the admin to reset the number of failed registrations. Use SetStatus to resume a
+    /// factory that stopped itself
+    ResetFailedRegistrations {},
+
+    /// Allows the admin to change the block size that responses are padded to
+    SetBlockSize { block_size: usize },
+
+    /// Allows the admin to let anyone, or only the owner, increment the counter of every offspring
+    /// that respects the factory's policy
+    SetPublicIncrements { allowed: bool },
+
+    /// Allows the admin to change the name that identifies the factory. Removes the name if
+    /// omitted
+    SetName { name: Option<String> },
+
+    /// Allows the admin to freeze offspring callbacks for maintenance. While frozen, offspring
+    /// registrations and deactivations are rejected, so CreateOffspring fails and offspring should
+    /// retry deactivating once callbacks are unfrozen. Unlike SetStatus, this also blocks existing
+    /// offspring
+    SetCallbacksFrozen { frozen: bool },
+
+    /// Allows the admin to rebuild an owner's active/inactive offspring lists from the recorded
+    /// offspring info. This iterates over all registered offspring.
+    RebuildOwnerIndex {
+        /// address of the owner whose lists are rebuilt
+        owner: String,
+    },
+
+    /// Allows the admin to grant/change an operator's permissions. Granting no permissions
+    /// removes the operator.
+    SetOperator {
+        /// address of the operator
+        address: String,
+        /// capabilities delegated to the operator
+        permissions: OperatorPermissions,
+    },
+
     /// disallow the use of a permit
     RevokePermit {
         /// name of the permit that is no longer valid
@@ -61,7 +261,8 @@ pub enum ExecuteMsg {
     },
 }
 
-/// Queries
+/// Queries. Authenticated queries prefer an address/viewing key pair over a permit when both are
+/// provided. Note that the offspring's queries prefer the permit instead
 #[derive(Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
@@ -76,12 +277,80 @@ pub enum QueryMsg {
         /// optional filter for only active or inactive offspring.  If not specified, lists all
         #[serde(default)]
         filter: Option<FilterTypes>,
-        /// start page for the offsprings returned and listed (applies to both active and inactive). Default: 0
+        /// start page for the offsprings returned and listed (applies to inactive too unless inactive_start_page is provided). Default: 0
         #[serde(default)]
         start_page: Option<u32>,
-        /// optional number of offspring to return in this page (applies to both active and inactive). Default: DEFAULT_PAGE_SIZE
+        /// optional number of offspring to return in this page (applies to inactive too unless inactive_page_size is provided). Default: DEFAULT_PAGE_SIZE
         #[serde(default)]
         page_size: Option<u32>,
+        /// optional start page for the inactive offspring only. Default: start_page
+        #[serde(default)]
+        inactive_start_page: Option<u32>,
+        /// optional number of inactive offspring to return in this page. Default: page_size
+        #[serde(default)]
+        inactive_page_size: Option<u32>,
+    },
+    /// lists the offspring whose owner is the given address, active ones first, resuming after the
+    /// `after` cursor instead of skipping pages
+    ListMyOffspringFrom {
+        /// address whose offspring to display
+        address: String,
+        /// viewing key
+        viewing_key: String,
+        /// address of the last offspring of the previous call. Starts from the beginning if omitted.
+        /// Nothing is listed if the address no longer owns it, in which case start over if
+        /// last_modified_height advanced
+        #[serde(default)]
+        after: Option<String>,
+        /// optional number of offspring to return. Default: DEFAULT_PAGE_SIZE
+        #[serde(default)]
+        limit: Option<u32>,
+    },
+    /// searches the offspring whose owner is the given address, active ones first, keeping only
+    /// those whose label contains `label_contains`
+    SearchMyOffspring {
+        /// address whose offspring to search
+        address: String,
+        /// viewing key
+        viewing_key: String,
+        /// optional substring the label must contain. If not specified, every label matches
+        #[serde(default)]
+        label_contains: Option<String>,
+        /// optional filter for only active or inactive offspring.  If not specified, searches all
+        #[serde(default)]
+        filter: Option<FilterTypes>,
+        /// start page of the matches returned. Default: 0
+        #[serde(default)]
+        start_page: Option<u32>,
+        /// optional number of matches to return in this page. Default: DEFAULT_PAGE_SIZE
+        #[serde(default)]
+        page_size: Option<u32>,
+    },
+    /// displays the last reported counts of the given offspring that the address owns. At most
+    /// MAX_COUNT_BATCH (50) offspring can be looked up at once. Offspring that do not report their
+    /// count are skipped.
+    BatchCounts {
+        /// offspring addresses to look up. Offspring not owned by address are skipped
+        addresses: Vec<String>,
+        /// address whose offspring are looked up
+        address: String,
+        /// viewing key
+        viewing_key: String,
+    },
+    /// displays the number of active and inactive offspring whose owner is the given address
+    OwnerSummary {
+        /// address whose offspring are counted
+        address: String,
+        /// viewing key
+        viewing_key: String,
+    },
+    /// authenticates address/key pair and displays the number of active and inactive offspring
+    /// whose owner is the given address
+    ValidateAndCount {
+        /// address whose offspring are counted
+        address: String,
+        /// viewing key
+        viewing_key: String,
     },
     /// lists all active offspring in reverse chronological order
     ListActiveOffspring {
@@ -101,6 +370,138 @@ pub enum QueryMsg {
         #[serde(default)]
         page_size: Option<u32>,
     },
+    /// lists all registered offspring regardless of their status
+    ListAllOffspring {
+        /// start page for the offsprings returned and listed. Default: 0
+        #[serde(default)]
+        start_page: Option<u32>,
+        /// optional number of offspring to return in this page. Default: DEFAULT_PAGE_SIZE
+        #[serde(default)]
+        page_size: Option<u32>,
+    },
+    /// lists the address and label of every registered offspring, a lighter alternative to
+    /// ListAllOffspring
+    ListLabels {
+        /// start page for the labels returned and listed. Default: 0
+        #[serde(default)]
+        start_page: Option<u32>,
+        /// optional number of labels to return in this page. Default: DEFAULT_PAGE_SIZE
+        #[serde(default)]
+        page_size: Option<u32>,
+    },
+    /// lists the registered offspring created within a block height range
+    ListByHeightRange {
+        /// lowest block height of the range, inclusive
+        from: u64,
+        /// highest block height of the range, inclusive
+        to: u64,
+        /// start page of the registered offspring scanned. Default: 0
+        #[serde(default)]
+        start_page: Option<u32>,
+        /// optional number of registered offspring to scan in this page. Default:
+        /// DEFAULT_PAGE_SIZE
+        #[serde(default)]
+        page_size: Option<u32>,
+    },
+    /// lists the registered offspring created with a category
+    ListByCategory {
+        /// category of the listed offspring
+        category: String,
+        /// start page of the registered offspring scanned. Default: 0
+        #[serde(default)]
+        start_page: Option<u32>,
+        /// optional number of registered offspring to scan in this page. Default:
+        /// DEFAULT_PAGE_SIZE
+        #[serde(default)]
+        page_size: Option<u32>,
+    },
+    /// lists every category offspring were created with
+    ListCategories {
+        /// start page for the categories returned and listed. Default: 0
+        #[serde(default)]
+        start_page: Option<u32>,
+        /// optional number of categories to return in this page. Default: DEFAULT_PAGE_SIZE
+        #[serde(default)]
+        page_size: Option<u32>,
+    },
+    /// lists the offspring whose owners renounced ownership
+    ListOwnerless {
+        /// start page for the offsprings returned and listed. Default: 0
+        #[serde(default)]
+        start_page: Option<u32>,
+        /// optional number of offspring to return in this page. Default: DEFAULT_PAGE_SIZE
+        #[serde(default)]
+        page_size: Option<u32>,
+    },
+    /// lists the offspring of several owners at once. Only the first DEFAULT_PAGE_SIZE active and
+    /// inactive offspring of each owner are listed, and each owner's answer tells whether more are
+    /// left. Can only be queried by the admin
+    ListForOwners {
+        /// addresses whose offspring are listed. At most MAX_OWNER_BATCH (10)
+        owners: Vec<String>,
+        /// optional filter for only active or inactive offspring. Default: All
+        #[serde(default)]
+        filter: Option<FilterTypes>,
+        /// admin's viewing key
+        admin_viewing_key: String,
+    },
+    /// lists the addresses that own offspring. Can only be queried by the admin
+    ListOwners {
+        /// admin's viewing key
+        admin_viewing_key: String,
+        /// start page for the owners returned and listed. Default: 0
+        #[serde(default)]
+        start_page: Option<u32>,
+        /// optional number of owners to return in this page. Default: DEFAULT_PAGE_SIZE
+        #[serde(default)]
+        page_size: Option<u32>,
+    },
+    /// lists all registered offspring in pages of up to MAX_ADMIN_PAGE_SIZE (1000) for bulk
+    /// exports. Only the admin can call it
+    AdminListAll {
+        /// admin's viewing key
+        admin_viewing_key: String,
+        /// start page for the offsprings returned and listed. Default: 0
+        #[serde(default)]
+        start_page: Option<u32>,
+        /// optional number of offspring to return in this page. Default: DEFAULT_PAGE_SIZE,
+        /// Maximum: MAX_ADMIN_PAGE_SIZE
+        #[serde(default)]
+        page_size: Option<u32>,
+    },
+    /// displays the number of distinct addresses that own offspring
+    OwnerCount {},
+    /// displays whether the given address is the admin
+    IsAdmin { address: String },
+    /// displays the factory's configuration
+    Config {},
+    /// displays offspring inventory and lifetime statistics
+    Stats {},
+    /// displays how many offspring were instantiated with each offspring code id. This iterates
+    /// over all registered offspring.
+    VersionBreakdown {},
+    /// displays the sum of the last reported counts of the registered offspring that report their
+    /// count. This iterates over all registered offspring, so its gas cost grows with their number.
+    TotalCount {},
+    /// lists the offspring with the highest reported counts in descending order. Only offspring
+    /// that report their count are ranked
+    TopOffspring {
+        /// number of offspring to list. Default and maximum: MAX_TOP_LIMIT
+        #[serde(default)]
+        limit: Option<u32>,
+    },
+    /// displays the code id and code hash of the offspring contract new offspring are created with
+    OffspringCode {},
+    /// displays the factory's own address and code hash
+    SelfInfo {},
+    /// displays the features this factory supports and its storage schema version, so clients
+    /// can adapt to the deployed factory
+    Capabilities {},
+    /// displays the code info and instantiate label CreateOffspring would use for this label
+    PreviewCreate {
+        /// label that would be given to CreateOffspring
+        label: String,
+    },
     /// authenticates the supplied address/viewing key. This should be called by offspring.
     IsKeyValid {
         /// address whose viewing key is being authenticated
@@ -108,8 +509,41 @@ pub enum QueryMsg {
         /// viewing key
         viewing_key: String,
     },
+    /// finds which of the candidate addresses the viewing key is valid for. At most MAX_KEY_BATCH
+    /// (20) candidates can be checked at once.
+    IsKeyValidFor {
+        /// viewing key
+        viewing_key: String,
+        /// addresses the key may belong to
+        candidates: Vec<String>,
+    },
+    /// authenticates a batch of address/viewing key pairs. At most MAX_KEY_BATCH (20) pairs can
+    /// be checked at once.
+    AreKeysValid {
+        /// (address, viewing key) pairs
+        pairs: Vec<(String, String)>,
+    },
     /// authenticates the supplied permit. This should be called by offspring.
     IsPermitValid { permit: Permit },
+    /// displays what the factory recorded about an offspring. This should be called by offspring.
+    GetMyInfo {
+        /// address of the offspring
+        offspring: String,
+    },
+    /// displays whether an offspring is registered and active. Unlike GetMyInfo, it does not fail
+    /// for an unregistered offspring, so offspring can use it to confirm their registration.
+    OffspringInfo {
+        /// address of the offspring
+        offspring: String,
+    },
+    /// displays whether an offspring is active, without scanning the lists
+    IsOffspringActive {
+        /// address of the offspring
+        address: String,
+    },
+    /// displays whether offspring that respect the factory's policy let anyone increment their
+    /// counter. This should be called by offspring.
+    PublicIncrementsAllowed {},
 }
 
 /// the filter types when viewing an address' offspring
@@ -129,25 +563,214 @@ pub enum QueryAnswer {
     ListMyOffspring {
         /// lists of the address' active offspring
         #[serde(skip_serializing_if = "Option::is_none")]
-        active: Option<Vec<StoreOffspringInfo>>,
+        active: Option<Vec<PublicOffspringInfo>>,
+        /// whether there is another page of active offspring
+        #[serde(skip_serializing_if = "Option::is_none")]
+        active_has_more: Option<bool>,
         /// lists of the address' inactive offspring
         #[serde(skip_serializing_if = "Option::is_none")]
-        inactive: Option<Vec<StoreOffspringInfo>>,
+        inactive: Option<Vec<PublicOffspringInfo>>,
+        /// whether there is another page of inactive offspring
+        #[serde(skip_serializing_if = "Option::is_none")]
+        inactive_has_more: Option<bool>,
+        /// listed addresses whose offspring info is missing. Omitted if there are none
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        orphans: Vec<Addr>,
+        /// block height of the factory's latest state change. The list is unchanged if it has not
+        /// advanced since the previous query
+        last_modified_height: u64,
+    },
+    /// List the offspring where address is associated, resuming after a cursor
+    ListMyOffspringFrom {
+        /// the address' offspring, active ones first
+        offspring: Vec<PublicOffspringInfo>,
+        /// cursor to pass as `after` to get the next offspring. Omitted if there are no more
+        #[serde(skip_serializing_if = "Option::is_none")]
+        next: Option<Addr>,
+        /// block height of the factory's latest state change. The list is unchanged if it has not
+        /// advanced since the previous query
+        last_modified_height: u64,
+    },
+    /// the address' offspring matching the search, active ones first
+    SearchMyOffspring {
+        offspring: Vec<PublicOffspringInfo>,
+        /// whether there is another page of matches
+        has_more: bool,
+        /// block height of the factory's latest state change. The list is unchanged if it has not
+        /// advanced since the previous query
+        last_modified_height: u64,
+    },
+    /// last reported counts of the requested offspring the address owns
+    BatchCounts { counts: Vec<OffspringCount> },
+    /// number of offspring the address owns
+    OwnerSummary {
+        /// number of the address' active offspring
+        active_count: u32,
+        /// number of the address' inactive offspring
+        inactive_count: u32,
+    },
+    /// result of authenticating address/key pair and the number of offspring the address owns
+    ValidateAndCount {
+        is_valid: bool,
+        /// number of the address' active offspring. Zero if the key is invalid
+        active_count: u32,
+        /// number of the address' inactive offspring. Zero if the key is invalid
+        inactive_count: u32,
     },
     /// List active offspring
     ListActiveOffspring {
-        /// active offspring
-        active: Vec<StoreOffspringInfo>,
+        /// page of active offspring
+        active: Page<PublicOffspringInfo>,
+        /// listed addresses whose offspring info is missing. Omitted if there are none
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        orphans: Vec<Addr>,
+        /// block height of the factory's latest state change. The list is unchanged if it has not
+        /// advanced since the previous query
+        last_modified_height: u64,
     },
     /// List inactive offspring in no particular order
     ListInactiveOffspring {
-        /// inactive offspring in no particular order
-        inactive: Vec<StoreOffspringInfo>,
+        /// page of inactive offspring in no particular order
+        inactive: Page<PublicOffspringInfo>,
+        /// listed addresses whose offspring info is missing. Omitted if there are none
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        orphans: Vec<Addr>,
+        /// block height of the factory's latest state change. The list is unchanged if it has not
+        /// advanced since the previous query
+        last_modified_height: u64,
+    },
+    /// List all registered offspring
+    ListAllOffspring {
+        /// active and inactive offspring
+        offspring: Vec<PublicOffspringInfo>,
+        /// block height of the factory's latest state change. The list is unchanged if it has not
+        /// advanced since the previous query
+        last_modified_height: u64,
+    },
+    /// (address, label) pairs of registered offspring
+    ListLabels {
+        /// (address, label) pairs in this page
+        labels: Vec<(Addr, String)>,
+        /// whether there are more registered offspring after this page
+        has_more: bool,
+    },
+    /// offspring created within a block height range
+    ListByHeightRange {
+        /// offspring in the scanned page that were created within the range
+        offspring: Vec<PublicOffspringInfo>,
+        /// whether there are more registered offspring to scan
+        has_more: bool,
+    },
+    /// offspring created with a category
+    ListByCategory {
+        /// offspring in the scanned page that were created with the category
+        offspring: Vec<PublicOffspringInfo>,
+        /// whether there are more registered offspring to scan
+        has_more: bool,
+    },
+    /// categories offspring were created with
+    ListCategories { categories: Vec<String> },
+    /// offspring whose owners renounced ownership
+    ListOwnerless { offspring: Vec<Addr> },
+    /// offspring of each of the listed owners, in the order the owners were given
+    ListForOwners { owners: Vec<OwnerOffspring> },
+    /// List the addresses that own offspring
+    ListOwners {
+        owners: Vec<Addr>,
+        /// block height of the factory's latest state change. The list is unchanged if it has not
+        /// advanced since the previous query
+        last_modified_height: u64,
+    },
+    /// page of all registered offspring
+    AdminListAll {
+        /// active and inactive offspring, including their owners
+        offspring: Vec<StoreOffspringInfo>,
+        /// number of registered offspring
+        total: u32,
+        /// whether there are more offspring after this page
+        has_more: bool,
+    },
+    /// number of distinct addresses that own offspring
+    OwnerCount { count: u32 },
+    /// whether the address is the admin
+    IsAdmin { is_admin: bool },
+    /// factory configuration
+    Config {
+        /// whether offspring creation is stopped
+        is_stopped: bool,
+        /// bounds of the initial count of new offspring
+        count_bounds: CountBounds,
+        /// whether new offspring can start with a negative count
+        allow_negative_count: bool,
+        /// whether new offspring are inactive until their owner activates them
+        require_activation: bool,
+        /// maximum number of offspring the factory can ever create
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_offspring: Option<u64>,
+        /// number of offspring the factory can still create. Omitted if there is no cap
+        #[serde(skip_serializing_if = "Option::is_none")]
+        remaining_capacity: Option<u64>,
+        /// block size responses are padded to
+        block_size: usize,
+        /// number of failed registrations the factory tolerates before stopping itself
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_failed_registrations: Option<u32>,
+        /// number of failed registrations since the admin last reset it
+        failed_registrations: u32,
+        /// whether offspring that respect the factory's policy let anyone increment their counter
+        public_increments_allowed: bool,
+        /// name that identifies the factory
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        /// whether offspring registrations and deactivations are frozen for maintenance
+        callbacks_frozen: bool,
+    },
+    /// offspring statistics
+    Stats {
+        /// number of active offspring
+        active_count: u32,
+        /// number of inactive offspring
+        inactive_count: u32,
+        /// number of offspring ever registered, regardless of their current status
+        total_ever_created: u64,
+    },
+    /// number of offspring per code id
+    VersionBreakdown {
+        /// (code_id, number of offspring) pairs in ascending code id order
+        versions: Vec<(u64, u32)>,
+    },
+    /// sum of the last reported counts of all registered offspring
+    TotalCount { total: i64 },
+    /// offspring with the highest reported counts
+    TopOffspring { offspring: Vec<OffspringCount> },
+    /// code info of the offspring contract used for new offspring
+    OffspringCode { code_info: CodeInfo },
+    /// the factory's own address and code hash
+    SelfInfo { contract: ContractInfo },
+    /// features the factory supports
+    Capabilities { capabilities: Capabilities },
+    /// configuration CreateOffspring would use
+    PreviewCreate {
+        /// code info the offspring would be instantiated with
+        code_info: CodeInfo,
+        /// label the offspring would be instantiated with
+        label: String,
     },
-    /// Viewing Key Error
-    ViewingKeyError { error: String },
     /// result of authenticating address/key pair
-    IsKeyValid { is_valid: bool },
+    IsKeyValid {
+        is_valid: bool,
+        /// the authenticated address if the key was valid
+        #[serde(skip_serializing_if = "Option::is_none")]
+        address: Option<Addr>,
+    },
+    /// the candidate address the viewing key is valid for
+    IsKeyValidFor {
+        /// the first candidate the key is valid for. Omitted if it is valid for none
+        #[serde(skip_serializing_if = "Option::is_none")]
+        address: Option<Addr>,
+    },
+    /// results of authenticating address/key pairs, in the order they were given
+    AreKeysValid { is_valid: Vec<bool> },
     /// result of authenticating a permit
     IsPermitValid {
         is_valid: bool,
@@ -155,6 +778,42 @@ pub enum QueryAnswer {
         #[serde(skip_serializing_if = "Option::is_none")]
         address: Option<Addr>,
     },
+    /// info the factory recorded about an offspring
+    GetMyInfo {
+        /// offspring info recorded at registration
+        info: PublicOffspringInfo,
+        /// whether the offspring is in the active list
+        is_active: bool,
+    },
+    /// whether an offspring is registered and active
+    OffspringInfo {
+        /// whether the offspring is registered
+        is_registered: bool,
+        /// whether the offspring is in the active list
+        is_active: bool,
+    },
+    /// whether an offspring is active
+    IsOffspringActive {
+        /// None if the offspring is not registered
+        is_active: Option<bool>,
+    },
+    /// whether offspring that respect the factory's policy let anyone increment their counter
+    PublicIncrementsAllowed { is_allowed: bool },
+}
+
+/// a page of a paginated list
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct Page<T> {
+    /// items in this page
+    pub items: Vec<T>,
+    /// page that was listed
+    pub start_page: u32,
+    /// maximum number of items in a page
+    pub page_size: u32,
+    /// number of items across all pages
+    pub total: u32,
+    /// whether there is another page
+    pub has_more: bool,
 }
 
 /// success or failure response
@@ -176,4 +835,29 @@ pub enum HandleAnswer {
         #[serde(skip_serializing_if = "Option::is_none")]
         message: Option<String>,
     },
+    /// response to a successful CreateViewingKey
+    ViewingKey {
+        /// the created viewing key
+        key: String,
+    },
+    /// response to a CreateViewingKey that did not create a key
+    CreateViewingKeyError {
+        /// why the key was not created
+        msg: String,
+    },
+    /// response to CreateOffspring
+    OffspringCreated {
+        /// address of the new offspring
+        address: Addr,
+        /// viewing key created for the owner if CreateOffspring asked for one
+        #[serde(skip_serializing_if = "Option::is_none")]
+        viewing_key: Option<String>,
+    },
+    /// response to RebuildOwnerIndex
+    RebuildOwnerIndex {
+        /// number of offspring now in the owner's active list
+        active: u32,
+        /// number of offspring now in the owner's inactive list
+        inactive: u32,
+    },
 }
@@ -1,8 +1,11 @@
 use cosmwasm_std::Addr;
-use secret_toolkit::utils::InitCallback;
+use secret_toolkit::utils::{HandleCallback, InitCallback};
 use serde::{Deserialize, Serialize};
 
-use crate::{state::BLOCK_SIZE, structs::ContractInfo};
+use crate::{
+    state::BLOCK_SIZE,
+    structs::{ContractInfo, Parity},
+};
 
 /// Instantiation message
 #[derive(Serialize, Deserialize)]
@@ -14,11 +17,44 @@ pub struct OffspringInstantiateMsg {
     /// Optional text description of this offspring
     #[serde(default)]
     pub description: Option<String>,
+    /// optional display name of the offspring
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
 
     pub owner: Addr,
     pub count: i32,
+    /// whether only the owner can increment the counter
+    pub owner_only_increment: bool,
+    /// optional parity every count must have
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_parity: Option<Parity>,
+    /// whether the offspring validates query permits itself
+    pub local_permit_validation: bool,
+    /// whether the offspring follows the factory's public increments policy
+    pub respect_factory_policy: bool,
+    /// whether the offspring reports its count to the factory
+    pub report_count: bool,
+    /// whether the offspring starts inactive because it awaits activation by its owner
+    pub start_inactive: bool,
+    /// id echoed back in the offspring's instantiate data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
 }
 
 impl InitCallback for OffspringInstantiateMsg {
     const BLOCK_SIZE: usize = BLOCK_SIZE;
 }
+
+/// Offspring handle messages to be used by the factory.
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OffspringExecuteMsg {
+    /// FactoryFreeze deactivates the offspring
+    FactoryFreeze {},
+    /// FactoryUnfreeze reactivates the offspring
+    FactoryUnfreeze {},
+}
+
+impl HandleCallback for OffspringExecuteMsg {
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+}
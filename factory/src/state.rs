@@ -1,39 +1,110 @@
 use cosmwasm_std::Addr;
 
-use secret_toolkit::{
-    serialization::Bincode2,
-    storage::{Item, Keymap, KeymapBuilder, Keyset, WithoutIter},
-};
+use secret_toolkit::storage::{Item, Keymap, Keyset};
 
-use crate::structs::{CodeInfo, StoreOffspringInfo};
+use crate::structs::{CodeInfo, CountBounds, OperatorPermissions, StoreOffspringInfo};
 
-/// pad handle responses and log attributes to blocks of 256 bytes to prevent leaking info based on
-/// response size
+/// default block size that handle and query responses are padded to in order to prevent leaking
+/// info based on response size
 pub const BLOCK_SIZE: usize = 256;
+/// version of the factory's storage layout. Bump it whenever stored data changes in a way clients
+/// need to know about
+pub const SCHEMA_VERSION: u32 = 1;
 /// the default number of offspring listed during queries
 pub const DEFAULT_PAGE_SIZE: u32 = 200;
+/// the maximum number of entries a public paginated query can list in one page
+pub const MAX_PAGE_SIZE: u32 = 500;
+/// the maximum number of offspring AdminListAll can list in one page
+pub const MAX_ADMIN_PAGE_SIZE: u32 = 1000;
+/// the maximum number of offspring TopOffspring can list
+pub const MAX_TOP_LIMIT: u32 = 50;
 /// This is the id offspring instantiate submessage returns upon reply
 pub const OFFSPRING_INSTANTIATE_REPLY_ID: u64 = 1;
+/// the maximum number of address/key pairs AreKeysValid can check
+pub const MAX_KEY_BATCH: usize = 20;
+/// the maximum number of offspring BatchCounts can look up
+pub const MAX_COUNT_BATCH: usize = 50;
+/// the maximum number of owners ListForOwners can list
+pub const MAX_OWNER_BATCH: usize = 10;
+/// the maximum length of an offspring label in bytes
+pub const MAX_LABEL_LEN: usize = 128;
+/// the maximum length of an offspring title in bytes
+pub const MAX_TITLE_LEN: usize = 128;
+/// the maximum length of an offspring description in bytes
+pub const MAX_DESCRIPTION_LEN: usize = 1024;
+/// the maximum length of an offspring category in bytes
+pub const MAX_CATEGORY_LEN: usize = 64;
+/// the maximum length of the factory name in bytes
+pub const MAX_NAME_LEN: usize = 64;
+/// the minimum number of entropy bytes required to create a viewing key
+pub const MIN_ENTROPY_LENGTH: usize = 8;
 /// Revoked permits prefix key
 pub const PREFIX_REVOKED_PERMITS: &str = "revoked_permits";
 
 /// whether or not the contract is stopped
 pub const IS_STOPPED: Item<bool> = Item::new(b"is_stopped");
+/// whether offspring registrations and deactivations are rejected for maintenance
+pub const CALLBACKS_FROZEN: Item<bool> = Item::new(b"callbacks_frozen");
 /// storage for the admin of the contract
 pub const ADMIN: Item<Addr> = Item::new(b"admin");
+/// storage for the factory's own code hash
+pub const SELF_CODE_HASH: Item<String> = Item::new(b"self_code_hash");
+/// storage for the operators the admin delegated some of its capabilities to
+pub static OPERATORS: Keymap<Addr, OperatorPermissions> = Keymap::new(b"operators");
 /// storage for the code_id and code_hash of the current offspring
 pub const OFFSPRING_CODE: Item<CodeInfo> = Item::new(b"offspring_version");
+/// storage of every offspring code version the factory was set to, keyed by code id. CreateOffspring
+/// can pick any of them
+pub static OFFSPRING_CODES: Keymap<u64, CodeInfo> = Keymap::new(b"offspring_versions");
+/// storage for the code id of the offspring being instantiated. Only kept until the offspring's
+/// instantiation reply
+pub const PENDING_CODE_ID: Item<u64> = Item::new(b"pending_code_id");
+/// storage for the category of the offspring being instantiated. Only kept until the offspring's
+/// instantiation reply
+pub const PENDING_CATEGORY: Item<String> = Item::new(b"pending_category");
+/// storage of every category offspring were ever created with
+pub static CATEGORIES: Keymap<String, bool> = Keymap::new(b"categories");
+/// storage for the bounds of the initial count of new offspring
+pub const COUNT_BOUNDS: Item<CountBounds> = Item::new(b"count_bounds");
+/// whether new offspring can start with a negative count
+pub const ALLOW_NEGATIVE_COUNT: Item<bool> = Item::new(b"allow_negative_count");
+/// whether new offspring start inactive until their owner activates them
+pub const REQUIRE_ACTIVATION: Item<bool> = Item::new(b"require_activation");
+/// storage for the optional maximum number of offspring the factory can ever create
+pub const MAX_OFFSPRING: Item<Option<u64>> = Item::new(b"max_offspring");
+/// storage for the optional number of failed registrations the factory tolerates before stopping
+pub const MAX_FAILED_REGISTRATIONS: Item<Option<u32>> = Item::new(b"max_failed_registrations");
+/// storage for the number of failed registrations since the admin last reset it
+pub const FAILED_REGISTRATIONS: Item<u32> = Item::new(b"failed_registrations");
+/// storage for the number of offspring ever registered. Never decremented
+pub const TOTAL_CREATED: Item<u64> = Item::new(b"total_created");
+/// whether offspring that respect the factory's policy let anyone increment their counter
+pub const PUBLIC_INCREMENTS_ALLOWED: Item<bool> = Item::new(b"public_increments_allowed");
+/// storage for the block height of the latest state change
+pub const LAST_MODIFIED_HEIGHT: Item<u64> = Item::new(b"last_modified_height");
+/// storage for the optional name that identifies the factory in registries
+pub const NAME: Item<Option<String>> = Item::new(b"name");
+/// storage for the entropy of the viewing key to create along with an offspring. Only kept until
+/// the offspring's instantiation reply, which only creates the key if the offspring is registered
+pub const PENDING_KEY_ENTROPY: Item<String> = Item::new(b"pending_key_entropy");
+/// storage for the block size handle and query responses are padded to
+pub const RESPONSE_BLOCK_SIZE: Item<usize> = Item::new(b"block_size");
 
 /// storage for all active/inactive offspring data. (HumanAddr refers to the address of the contract)
-pub static OFFSPRING_STORAGE: Keymap<Addr, StoreOffspringInfo, Bincode2, WithoutIter> =
-    KeymapBuilder::new(b"offspring_store")
-        .without_iter()
-        .build();
+pub static OFFSPRING_STORAGE: Keymap<Addr, StoreOffspringInfo> = Keymap::new(b"offspring_store");
 /// storage of all active offspring addresses
 pub static ACTIVE_STORE: Keyset<Addr> = Keyset::new(b"active");
 /// storage of all inactive offspring addresses
 pub static INACTIVE_STORE: Keyset<Addr> = Keyset::new(b"inactive");
+/// storage of registered offspring that are waiting for their owner to activate them
+pub static PENDING_ACTIVATION: Keyset<Addr> = Keyset::new(b"pending_activation");
+/// storage of every address that owns an offspring. An owner is removed when their last offspring
+/// is purged
+pub static OWNERS_SET: Keymap<Addr, bool> = Keymap::new(b"owners_set");
 /// owner's active offspring storage. Meant to be used with a suffix of the user's address.
 pub static OWNERS_ACTIVE: Keyset<Addr> = Keyset::new(b"owners_active");
 /// owner's inactive offspring storage. Meant to be used with a suffix of the user's address.
 pub static OWNERS_INACTIVE: Keyset<Addr> = Keyset::new(b"owners_inactive");
+/// storage of the offspring whose owners renounced ownership. They are no longer in any owner's
+/// lists
+pub static OWNERLESS: Keyset<Addr> = Keyset::new(b"ownerless");
@@ -5,6 +5,14 @@ use cosmwasm_std::Addr;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// parity an offspring's count can be restricted to
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
 /// Info needed to instantiate an offspring
 #[derive(Serialize, Deserialize, JsonSchema)]
 pub struct CodeInfo {
@@ -14,6 +22,58 @@ pub struct CodeInfo {
     pub code_hash: String,
 }
 
+/// optional bounds for the initial count of new offspring
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, Default)]
+pub struct CountBounds {
+    /// minimum initial count (inclusive)
+    pub min: Option<i32>,
+    /// maximum initial count (inclusive)
+    pub max: Option<i32>,
+}
+
+impl CountBounds {
+    /// returns true if the count is within the bounds
+    pub fn contains(&self, count: i32) -> bool {
+        !matches!(self.min, Some(min) if count < min)
+            && !matches!(self.max, Some(max) if count > max)
+    }
+}
+
+/// capabilities the admin has delegated to an operator
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, Default)]
+pub struct OperatorPermissions {
+    /// whether the operator can stop/resume the creation of new offspring
+    pub can_pause: bool,
+    /// whether the operator can update the offspring contract version
+    pub can_set_code: bool,
+}
+
+impl OperatorPermissions {
+    /// returns true if no capability is granted
+    pub fn is_empty(&self) -> bool {
+        !(self.can_pause || self.can_set_code)
+    }
+}
+
+/// features a deployed factory supports
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct Capabilities {
+    /// version of the factory's storage layout
+    pub schema_version: u32,
+    /// whether queries can be authenticated with permits instead of viewing keys
+    pub permits: bool,
+    /// whether CreateOffspring can also create a viewing key for the owner
+    pub auto_create_key: bool,
+    /// whether CreateOffspring can pick an earlier offspring code version
+    pub code_versions: bool,
+    /// whether offspring can currently be created
+    pub creation_enabled: bool,
+    /// whether offspring registrations and deactivations are currently accepted
+    pub callbacks_enabled: bool,
+    /// whether offspring that respect the factory's policy let anyone increment their counter
+    pub public_increments_allowed: bool,
+}
+
 /// code hash and address of a contract
 #[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub struct ContractInfo {
@@ -30,20 +90,47 @@ pub struct ContractInfo {
 pub struct ReplyOffspringInfo {
     /// label used when initializing offspring
     pub label: String,
+    /// display name the offspring was initialized with
+    #[serde(default)]
+    pub title: Option<String>,
     pub owner: Addr,
     pub address: Addr,
     pub code_hash: String,
+    /// the count the offspring was initialized with. 0 if the offspring does not keep a count
+    #[serde(default)]
+    pub count: i32,
+    /// whether the offspring reports its count to the factory
+    #[serde(default)]
+    pub report_count: bool,
+    /// correlation id given to CreateOffspring, echoed back by the offspring
+    #[serde(default)]
+    pub correlation_id: Option<String>,
 }
 
 impl ReplyOffspringInfo {
-    /// takes the register offspring information and creates a store offspring info struct
-    pub fn to_store_offspring_info(&self) -> StoreOffspringInfo {
+    /// takes the register offspring information, the code id the offspring was instantiated
+    /// with, the block height it was registered at and its optional category, and creates a
+    /// store offspring info struct
+    pub fn to_store_offspring_info(
+        &self,
+        code_id: u64,
+        created_height: u64,
+        category: Option<String>,
+    ) -> StoreOffspringInfo {
         StoreOffspringInfo {
             contract: ContractInfo {
                 code_hash: self.code_hash.clone(),
                 address: self.address.clone(),
             },
             label: self.label.clone(),
+            title: self.title.clone(),
+            owner: self.owner.clone(),
+            // the count of an offspring that does not report it is not recorded at all
+            last_count: if self.report_count { self.count } else { 0 },
+            code_id,
+            created_height,
+            category,
+            reports_count: self.report_count,
         }
     }
 }
@@ -55,6 +142,87 @@ pub struct StoreOffspringInfo {
     pub contract: ContractInfo,
     /// label used when initializing offspring
     pub label: String,
+    /// display name the offspring was initialized with. Later changes by the owner are not
+    /// reflected here. Always serialized, since bincode can not decode skipped fields
+    pub title: Option<String>,
+    /// owner of the offspring recorded at registration. Only shown to the admin
+    pub owner: Addr,
+    /// the last count the offspring reported to the factory. 0 if it does not report its count
+    pub last_count: i32,
+    /// code id of the offspring contract version the offspring was instantiated with
+    pub code_id: u64,
+    /// block height the offspring was registered at
+    pub created_height: u64,
+    /// optional category the offspring was created with. Always serialized, since bincode can not
+    /// decode skipped fields
+    pub category: Option<String>,
+    /// whether the offspring opted in to reporting its count. Only reported counts are shown
+    pub reports_count: bool,
+}
+
+/// offspring info shown in query answers. Unlike StoreOffspringInfo, it does not reveal the owner
+/// or the last count
+#[derive(Serialize, Deserialize, Clone, JsonSchema, Debug)]
+pub struct PublicOffspringInfo {
+    /// offspring address
+    pub contract: ContractInfo,
+    /// label used when initializing offspring
+    pub label: String,
+    /// display name the offspring was initialized with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// code id of the offspring contract version the offspring was instantiated with
+    pub code_id: u64,
+    /// block height the offspring was registered at
+    #[serde(default)]
+    pub created_height: u64,
+    /// optional category the offspring was created with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+}
+
+impl StoreOffspringInfo {
+    /// takes the stored offspring info and creates the info shown in query answers
+    pub fn to_public_offspring_info(&self) -> PublicOffspringInfo {
+        PublicOffspringInfo {
+            contract: self.contract.clone(),
+            label: self.label.clone(),
+            title: self.title.clone(),
+            code_id: self.code_id,
+            created_height: self.created_height,
+            category: self.category.clone(),
+        }
+    }
+}
+
+/// the offspring an owner has
+#[derive(Serialize, Deserialize, Clone, JsonSchema, Debug)]
+pub struct OwnerOffspring {
+    /// address of the owner
+    pub owner: Addr,
+    /// owner's active offspring, if active offspring were listed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<Vec<PublicOffspringInfo>>,
+    /// whether the owner has more active offspring than were listed, if active offspring were
+    /// listed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_has_more: Option<bool>,
+    /// owner's inactive offspring, if inactive offspring were listed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inactive: Option<Vec<PublicOffspringInfo>>,
+    /// whether the owner has more inactive offspring than were listed, if inactive offspring were
+    /// listed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inactive_has_more: Option<bool>,
+}
+
+/// the address and last reported count of an offspring that reports its count
+#[derive(Serialize, Deserialize, Clone, JsonSchema, Debug)]
+pub struct OffspringCount {
+    /// offspring address
+    pub address: Addr,
+    /// the last count the offspring reported to the factory
+    pub count: i32,
 }
 
 impl CodeInfo {
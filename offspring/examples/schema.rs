@@ -3,7 +3,7 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use offspring::msg::{ExecuteMsg, InstantiateMsg, QueryAnswer, QueryMsg};
+use offspring::msg::{ExecuteMsg, HandleAnswer, InstantiateMsg, QueryAnswer, QueryMsg};
 use offspring::state::State;
 
 fn main() {
@@ -14,6 +14,7 @@ fn main() {
 
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(HandleAnswer), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(State), &out_dir);
     export_schema(&schema_for!(QueryAnswer), &out_dir);
@@ -1,16 +1,30 @@
+#[cfg(feature = "counter")]
+use cosmwasm_std::CosmosMsg;
 use cosmwasm_std::{
-    entry_point, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, Storage,
+    entry_point, to_binary, Addr, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo, Response,
+    Storage,
 };
-use secret_toolkit::permit::Permit;
+use secret_toolkit::permit::{validate, Permit, RevokedPermits};
 use secret_toolkit::utils::{HandleCallback, Query};
 
 use crate::error::ContractError;
+#[cfg(feature = "counter")]
+use crate::factory_msg::PublicIncrementsAllowedWrapper;
 use crate::factory_msg::{
-    FactoryExecuteMsg, FactoryOffspringInfo, FactoryQueryMsg, IsKeyValidWrapper,
-    IsPermitValidWrapper,
+    FactoryExecuteMsg, FactoryOffspringInfo, FactoryQueryMsg, GetMyInfo, GetMyInfoWrapper,
+    IsKeyValidWrapper, IsPermitValidWrapper, OffspringInfoWrapper,
+};
+use crate::msg::{ContractInfo, ExecuteMsg, HandleAnswer, InstantiateMsg, QueryAnswer, QueryMsg};
+#[cfg(feature = "counter")]
+use crate::state::{
+    CountChange, COUNT_HISTORY, DEFAULT_PAGE_SIZE, HISTORY_NEXT, INITIAL_COUNT, IS_LOCKED,
+    MAX_HISTORY_LEN, MAX_PAGE_SIZE,
+};
+use crate::state::{
+    State, EXPIRY, FACTORY_INFO, IS_ACTIVE, IS_REGISTERED, MAX_DESCRIPTION_LEN, MAX_KEY_HINT_LEN,
+    MAX_LABEL_LEN, MAX_META_KEYS, MAX_META_KEY_LEN, MAX_META_VALUE_LEN, MAX_TITLE_LEN, META, OWNER,
+    PREFIX_REVOKED_PERMITS, STATE,
 };
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryAnswer, QueryMsg};
-use crate::state::{State, FACTORY_INFO, IS_ACTIVE, OWNER, STATE};
 
 ////////////////////////////////////// Init ///////////////////////////////////////
 /// Returns Result<Response, ContractError>
@@ -30,23 +44,55 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    if msg.label.len() > MAX_LABEL_LEN {
+        return Err(ContractError::LabelTooLong { max: MAX_LABEL_LEN });
+    }
+    enforce_title_len(&msg.title)?;
+    if matches!(&msg.description, Some(desc) if desc.len() > MAX_DESCRIPTION_LEN) {
+        return Err(ContractError::DescriptionTooLong {
+            max: MAX_DESCRIPTION_LEN,
+        });
+    }
+    validate_factory(&msg.factory)?;
     FACTORY_INFO.save(deps.storage, &msg.factory)?;
     OWNER.save(deps.storage, &msg.owner)?;
-    IS_ACTIVE.save(deps.storage, &true)?;
+    IS_ACTIVE.save(deps.storage, &!msg.start_inactive)?;
 
     let state = State {
         label: msg.label.clone(),
+        title: msg.title.clone(),
         description: msg.description,
+        #[cfg(feature = "counter")]
         count: msg.count,
+        #[cfg(feature = "counter")]
+        owner_only_increment: msg.owner_only_increment,
+        #[cfg(feature = "counter")]
+        allowed_parity: msg.allowed_parity,
+        local_permit_validation: msg.local_permit_validation,
+        owner_can_set_factory: msg.owner_can_set_factory,
+        #[cfg(feature = "counter")]
+        respect_factory_policy: msg.respect_factory_policy,
+        #[cfg(feature = "counter")]
+        report_count: msg.report_count,
     };
+    #[cfg(feature = "counter")]
+    enforce_parity(&state, state.count)?;
     STATE.save(deps.storage, &state)?;
+    #[cfg(feature = "counter")]
+    INITIAL_COUNT.save(deps.storage, &state.count)?;
 
     // perform register callback to factory
     let offspring_info = FactoryOffspringInfo {
         label: msg.label,
+        title: msg.title,
         owner: msg.owner,
         address: env.contract.address,
         code_hash: env.contract.code_hash,
+        #[cfg(feature = "counter")]
+        count: msg.count,
+        #[cfg(feature = "counter")]
+        report_count: msg.report_count,
+        correlation_id: msg.correlation_id,
     };
 
     Ok(Response::new().set_data(to_binary(&offspring_info)?))
@@ -58,43 +104,99 @@ pub fn instantiate(
 /// # Arguments
 ///
 /// * `deps` - DepsMut containing all the contract's external dependencies
-/// * `_env` - Env of contract's environment
+/// * `env`  - Env of contract's environment
 /// * `info` - Carries the info of who sent the message and how much native funds were sent along
 /// * `msg`  - HandleMsg passed in with the execute message
 #[entry_point]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Increment {} => try_increment(deps),
-        ExecuteMsg::Reset { count } => try_reset(deps, info, count),
-        ExecuteMsg::Deactivate {} => try_deactivate(deps, info),
+        #[cfg(feature = "counter")]
+        ExecuteMsg::Increment {} => try_increment(deps, env, info),
+        #[cfg(feature = "counter")]
+        ExecuteMsg::IncrementBy { steps } => try_increment_by(deps, env, info, steps),
+        #[cfg(feature = "counter")]
+        ExecuteMsg::Reset { count } => try_reset(deps, env, info, count),
+        #[cfg(feature = "counter")]
+        ExecuteMsg::Clear {} => try_reset(deps, env, info, 0),
+        #[cfg(feature = "counter")]
+        ExecuteMsg::Lock {} => try_lock(deps, info),
+        #[cfg(feature = "counter")]
+        ExecuteMsg::CompareAndSet { expected, new } => {
+            try_compare_and_set(deps, env, info, expected, new)
+        }
+        ExecuteMsg::Deactivate { force } => try_deactivate(deps, env, info, force),
+        ExecuteMsg::Withdraw { to } => try_withdraw(deps, env, info, to),
+        ExecuteMsg::TransferOwnership {
+            new_owner,
+            new_owner_key_hint,
+        } => try_transfer_ownership(deps, info, new_owner, new_owner_key_hint),
+        ExecuteMsg::RenounceOwnership {} => try_renounce_ownership(deps, env, info),
+        ExecuteMsg::SetMeta { key, value } => try_set_meta(deps, info, key, value),
+        ExecuteMsg::RemoveMeta { key } => try_remove_meta(deps, info, key),
+        ExecuteMsg::RevokePermit { permit_name } => try_revoke_permit(deps, info, permit_name),
+        ExecuteMsg::Retire {} => try_retire(deps, info),
+        ExecuteMsg::FactoryFreeze {} => try_factory_set_active(deps, info, false),
+        ExecuteMsg::FactoryUnfreeze {} => try_factory_set_active(deps, info, true),
+        ExecuteMsg::SetFactory { factory } => try_set_factory(deps, info, factory),
+        ExecuteMsg::SetTitle { title } => try_set_title(deps, info, title),
+        ExecuteMsg::SetDescription { description } => try_set_description(deps, info, description),
+        ExecuteMsg::SetExpiry { height } => try_set_expiry(deps, info, height),
+        ExecuteMsg::AppendDescription { text } => try_append_description(deps, info, text),
+        ExecuteMsg::ConfirmRegistered {} => try_confirm_registered(deps, env),
     }
 }
 
 /// Returns Result<Response, ContractError>
 ///
-/// deactivates the offspring and lets the factory know.
+/// deactivates the offspring and lets the factory know. The factory is told in the same
+/// transaction, so if it rejects the deactivation, the deactivation is reverted as well.
 ///
 /// # Arguments
 ///
-/// * `deps` - DepsMut containing all the contract's external dependencies
-/// * `info` - Carries the info of who sent the message and how much native funds were sent along
-pub fn try_deactivate(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+/// * `deps`  - DepsMut containing all the contract's external dependencies
+/// * `env`   - Env of contract's environment
+/// * `info`  - Carries the info of who sent the message and how much native funds were sent along
+/// * `force` - whether to deactivate even though the offspring still holds funds
+pub fn try_deactivate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    force: bool,
+) -> Result<Response, ContractError> {
     // let mut state: State = load(deps.storage, CONFIG_KEY)?;
-    enforce_active(deps.storage)?;
+    // an expired offspring can still be deactivated, so that the factory learns about it
+    if !IS_ACTIVE.load(deps.storage)? {
+        return Err(ContractError::Inactive {});
+    }
     let owner = OWNER.load(deps.storage)?;
     if info.sender != owner {
         return Err(ContractError::Unauthorized {});
     }
+    if !force
+        && !deps
+            .querier
+            .query_all_balances(env.contract.address)?
+            .is_empty()
+    {
+        return Err(ContractError::FundsRemaining {});
+    }
     IS_ACTIVE.save(deps.storage, &false)?;
 
     // let factory know
     let factory = FACTORY_INFO.load(deps.storage)?;
-    let deactivate_msg = FactoryExecuteMsg::DeactivateOffspring { owner }.to_cosmos_msg(
+    // only an offspring that reports its count tells the factory its final count
+    #[cfg(feature = "counter")]
+    let final_count = Some(STATE.load(deps.storage)?)
+        .filter(|state| state.report_count)
+        .map(|state| state.count);
+    #[cfg(not(feature = "counter"))]
+    let final_count = None;
+    let deactivate_msg = FactoryExecuteMsg::DeactivateOffspring { final_count }.to_cosmos_msg(
         factory.code_hash,
         factory.address.to_string(),
         None,
@@ -105,159 +207,1537 @@ pub fn try_deactivate(deps: DepsMut, info: MessageInfo) -> Result<Response, Cont
 
 /// Returns Result<Response, ContractError>
 ///
-/// increases the counter. Can be executed by anyone.
+/// makes the new owner the owner of this offspring and lets the factory know. The factory is told
+/// in the same transaction, so if it rejects the transfer, the transfer is reverted. Since
+/// authenticated queries check the current owner, the old owner loses access as soon as the
+/// new owner gains it. Can only be executed by owner.
+///
+/// # Arguments
+///
+/// * `deps`               - DepsMut containing all the contract's external dependencies
+/// * `info`               - Carries the info of who sent the message and how much native funds were sent along
+/// * `new_owner`          - address of the new owner
+/// * `new_owner_key_hint` - optional hint logged for front ends to prompt the new owner to set a
+///   viewing key
+pub fn try_transfer_ownership(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_owner: String,
+    new_owner_key_hint: Option<String>,
+) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    if matches!(&new_owner_key_hint, Some(hint) if hint.len() > MAX_KEY_HINT_LEN) {
+        return Err(ContractError::KeyHintTooLong {
+            max: MAX_KEY_HINT_LEN,
+        });
+    }
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+    OWNER.save(deps.storage, &new_owner)?;
+
+    let factory = FACTORY_INFO.load(deps.storage)?;
+    let transfer_msg = FactoryExecuteMsg::TransferOffspring {
+        new_owner: new_owner.clone(),
+    }
+    .to_cosmos_msg(factory.code_hash, factory.address.to_string(), None)?;
+
+    let mut response = Response::new()
+        .add_message(transfer_msg)
+        .add_attribute("new_owner", new_owner);
+    if let Some(hint) = new_owner_key_hint {
+        response = response.add_attribute("new_owner_key_hint", hint);
+    }
+    Ok(response)
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// permanently gives up ownership of this offspring and lets the factory know. The offspring's own
+/// address becomes its owner, so no one can call the owner-only messages anymore. Since no one
+/// could withdraw them afterwards, it fails while this contract holds funds. Can only be executed
+/// by owner.
 ///
 /// # Arguments
 ///
 /// * `deps` - DepsMut containing all the contract's external dependencies
-pub fn try_increment(deps: DepsMut) -> Result<Response, ContractError> {
-    enforce_active(deps.storage)?;
-    let mut state = STATE.load(deps.storage)?;
-    state.count += 1;
-    STATE.save(deps.storage, &state)?;
+/// * `env`  - Env of contract's environment
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+pub fn try_renounce_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !deps
+        .querier
+        .query_all_balances(env.contract.address.clone())?
+        .is_empty()
+    {
+        return Err(ContractError::RenounceFundsRemaining {});
+    }
+    OWNER.save(deps.storage, &env.contract.address)?;
 
-    Ok(Response::new())
+    let factory = FACTORY_INFO.load(deps.storage)?;
+    let renounce_msg = FactoryExecuteMsg::RenounceOffspring {}.to_cosmos_msg(
+        factory.code_hash,
+        factory.address.to_string(),
+        None,
+    )?;
+
+    Ok(Response::new().add_message(renounce_msg))
 }
 
 /// Returns Result<Response, ContractError>
 ///
-/// resets the counter to count. Can only be executed by owner.
+/// sets the metadata value of a key. Can only be executed by owner.
 ///
 /// # Arguments
 ///
 /// * `deps`  - DepsMut containing all the contract's external dependencies
 /// * `info`  - Carries the info of who sent the message and how much native funds were sent along
-/// * `count` - The value to reset the counter to.
-pub fn try_reset(deps: DepsMut, info: MessageInfo, count: i32) -> Result<Response, ContractError> {
-    enforce_active(deps.storage)?;
-    let mut state = STATE.load(deps.storage)?;
+/// * `key`   - metadata key
+/// * `value` - new value of the key
+pub fn try_set_meta(
+    deps: DepsMut,
+    info: MessageInfo,
+    key: String,
+    value: String,
+) -> Result<Response, ContractError> {
     if info.sender != OWNER.load(deps.storage)? {
         return Err(ContractError::Unauthorized {});
     }
-    state.count = count;
-    STATE.save(deps.storage, &state)?;
+    if key.len() > MAX_META_KEY_LEN {
+        return Err(ContractError::MetaKeyTooLong {
+            max: MAX_META_KEY_LEN,
+        });
+    }
+    if value.len() > MAX_META_VALUE_LEN {
+        return Err(ContractError::MetaValueTooLong {
+            max: MAX_META_VALUE_LEN,
+        });
+    }
+    if !META.contains(deps.storage, &key) && META.get_len(deps.storage)? >= MAX_META_KEYS {
+        return Err(ContractError::TooManyMetaKeys { max: MAX_META_KEYS });
+    }
+    META.insert(deps.storage, &key, &value)?;
 
     Ok(Response::new())
 }
 
-/////////////////////////////////////// Query /////////////////////////////////////
-/// Returns Result<Binary, ContractError>
+/// Returns Result<Response, ContractError>
+///
+/// removes the metadata value of a key. Removing a key that is not set does nothing. Can only be
+/// executed by owner.
 ///
 /// # Arguments
 ///
-/// * `deps` - Deps containing all the contract's external dependencies
-/// * `_env` - Env of contract's environment
-/// * `msg`  - QueryMsg passed in with the query call
-#[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
-    match msg {
-        QueryMsg::GetCount {
-            address,
-            viewing_key,
-            permit,
-        } => Ok(to_binary(&query_count(
-            deps,
-            permit,
-            address,
-            viewing_key,
-        )?)?),
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+/// * `key`  - metadata key
+pub fn try_remove_meta(
+    deps: DepsMut,
+    info: MessageInfo,
+    key: String,
+) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    if META.contains(deps.storage, &key) {
+        META.remove(deps.storage, &key)?;
     }
+
+    Ok(Response::new())
 }
 
-/// Returns Result<QueryAnswer, ContractError> displaying the count.
+/// Returns Result<Response, ContractError>
+///
+/// revokes the owner's query permits with the given name when this offspring validates permits
+/// itself. Permits revoked in the factory are not seen by local validation. Can only be executed
+/// by owner.
 ///
 /// # Arguments
 ///
-/// * `deps`        - Deps containing all the contract's external dependencies
-/// * `permit`      - optional query permit to authenticate the query request. This or viewing key must be provided.
-/// * `address`     - optional address whose viewing key is being validated.
-/// * `viewing_key` - Optional string key used to authenticate the query.
-fn query_count(
-    deps: Deps,
-    permit: Option<Permit>,
-    address: Option<String>,
-    viewing_key: Option<String>,
-) -> Result<QueryAnswer, ContractError> {
-    let addr = if let (Some(address), Some(viewing_key)) = (address, viewing_key) {
-        let addr = deps.api.addr_validate(&address)?;
-        enforce_valid_viewing_key(deps, &addr, viewing_key)?;
-        addr
-    } else if let Some(permit) = permit {
-        enforce_valid_permit(deps, permit)?
-    } else {
+/// * `deps`        - DepsMut containing all the contract's external dependencies
+/// * `info`        - Carries the info of who sent the message and how much native funds were sent along
+/// * `permit_name` - name of the permits that are no longer valid
+pub fn try_revoke_permit(
+    deps: DepsMut,
+    info: MessageInfo,
+    permit_name: String,
+) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
         return Err(ContractError::Unauthorized {});
-    };
+    }
+    RevokedPermits::revoke_permit(
+        deps.storage,
+        PREFIX_REVOKED_PERMITS,
+        info.sender.as_ref(),
+        &permit_name,
+    );
 
-    if OWNER.load(deps.storage)? == addr {
-        let state: State = STATE.load(deps.storage)?;
-        Ok(QueryAnswer::CountResponse { count: state.count })
-    } else {
-        Err(ContractError::Unauthorized {})
+    Ok(Response::new())
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// sends all funds the offspring holds to the given address. Can only be executed by owner.
+///
+/// # Arguments
+///
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `env`  - Env of contract's environment
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+/// * `to`   - address the funds are sent to
+pub fn try_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    to: String,
+) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let to = deps.api.addr_validate(&to)?;
+    let amount = deps.querier.query_all_balances(env.contract.address)?;
+    if amount.is_empty() {
+        return Ok(Response::new());
     }
+
+    Ok(Response::new().add_message(BankMsg::Send {
+        to_address: to.to_string(),
+        amount,
+    }))
 }
 
-/// Returns Result<(), ContractError>
+/// Returns Result<Response, ContractError>
 ///
-/// makes sure that the address and the viewing key match in the factory contract.
+/// asks the factory to purge this inactive offspring from its records. Can only be executed by
+/// owner.
 ///
 /// # Arguments
 ///
-/// * `deps`        - Deps containing all the contract's external dependencies.
-/// * `state`       - a reference to the State of the contract.
-/// * `address`     - a reference to the address whose viewing key is being validated.
-/// * `viewing_key` - String key used to authenticate a query.
-fn enforce_valid_viewing_key(
-    deps: Deps,
-    address: &Addr,
-    viewing_key: String,
-) -> Result<(), ContractError> {
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+pub fn try_retire(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    if IS_ACTIVE.load(deps.storage)? {
+        return Err(ContractError::CustomError {
+            val: "Only an inactive offspring can be retired".to_string(),
+        });
+    }
+    let owner = OWNER.load(deps.storage)?;
+    if info.sender != owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
     let factory = FACTORY_INFO.load(deps.storage)?;
-    let key_valid_msg = FactoryQueryMsg::IsKeyValid {
-        address: address.clone(),
-        viewing_key,
+    let purge_msg = FactoryExecuteMsg::PurgeOffspring {}.to_cosmos_msg(
+        factory.code_hash,
+        factory.address.to_string(),
+        None,
+    )?;
+
+    Ok(Response::new().add_message(purge_msg))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// deactivates or reactivates the offspring on the factory's behalf. Can only be executed by the
+/// factory, which already knows about the change, so it is not notified.
+///
+/// # Arguments
+///
+/// * `deps`   - DepsMut containing all the contract's external dependencies
+/// * `info`   - Carries the info of who sent the message and how much native funds were sent along
+/// * `active` - whether the offspring should be active
+pub fn try_factory_set_active(
+    deps: DepsMut,
+    info: MessageInfo,
+    active: bool,
+) -> Result<Response, ContractError> {
+    if info.sender != FACTORY_INFO.load(deps.storage)?.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    IS_ACTIVE.save(deps.storage, &active)?;
+
+    Ok(Response::new())
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// points the offspring to a new factory. Can only be executed by the current factory, or by the
+/// owner if the offspring was created with owner_can_set_factory.
+///
+/// # Arguments
+///
+/// * `deps`    - DepsMut containing all the contract's external dependencies
+/// * `info`    - Carries the info of who sent the message and how much native funds were sent along
+/// * `factory` - code hash and address of the new factory
+pub fn try_set_factory(
+    deps: DepsMut,
+    info: MessageInfo,
+    factory: ContractInfo,
+) -> Result<Response, ContractError> {
+    let old_factory = FACTORY_INFO.load(deps.storage)?;
+    let owner_allowed =
+        STATE.load(deps.storage)?.owner_can_set_factory && OWNER.load(deps.storage)? == info.sender;
+    if info.sender != old_factory.address && !owner_allowed {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_factory(&factory)?;
+    let new_factory = ContractInfo {
+        code_hash: factory.code_hash,
+        address: deps.api.addr_validate(factory.address.as_str())?,
     };
-    let key_valid_response: IsKeyValidWrapper =
-        key_valid_msg.query(deps.querier, factory.code_hash, factory.address.to_string())?;
-    // if authenticated
-    if key_valid_response.is_key_valid.is_valid {
-        Ok(())
-    } else {
-        Err(ContractError::ViewingKeyOrUnauthorized {})
+    FACTORY_INFO.save(deps.storage, &new_factory)?;
+
+    Ok(Response::new()
+        .add_attribute("old_factory", old_factory.address)
+        .add_attribute("new_factory", new_factory.address))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// changes the display name of the offspring. Can only be executed by owner. The factory keeps the
+/// title the offspring was created with.
+///
+/// # Arguments
+///
+/// * `deps`  - DepsMut containing all the contract's external dependencies
+/// * `info`  - Carries the info of who sent the message and how much native funds were sent along
+/// * `title` - new display name. Removes the title if omitted
+pub fn try_set_title(
+    deps: DepsMut,
+    info: MessageInfo,
+    title: Option<String>,
+) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
     }
+    enforce_title_len(&title)?;
+    let mut state = STATE.load(deps.storage)?;
+    state.title = title;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new())
 }
 
-/// Returns Result<Addr, ContractError>, the address of the permit's signer
+/// Returns Result<Response, ContractError>
+///
+/// replaces the description of the offspring. Can only be executed by owner.
 ///
 /// # Arguments
 ///
-/// * `deps`   - Deps containing all the contract's external dependencies
-/// * `permit` - permit offered for authentication
-fn enforce_valid_permit(deps: Deps, permit: Permit) -> Result<Addr, ContractError> {
-    let factory = FACTORY_INFO.load(deps.storage)?;
-    let permit_valid_msg = FactoryQueryMsg::IsPermitValid { permit };
-    let permit_valid_resp: IsPermitValidWrapper =
-        permit_valid_msg.query(deps.querier, factory.code_hash, factory.address.to_string())?;
-    if permit_valid_resp.is_key_valid.is_valid {
-        permit_valid_resp
-            .is_key_valid
-            .address
-            .ok_or(ContractError::Unauthorized {})
-    } else {
-        Err(ContractError::Unauthorized {})
+/// * `deps`        - DepsMut containing all the contract's external dependencies
+/// * `info`        - Carries the info of who sent the message and how much native funds were sent along
+/// * `description` - new description. Removes the description if omitted
+pub fn try_set_description(
+    deps: DepsMut,
+    info: MessageInfo,
+    description: Option<String>,
+) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
     }
+    if matches!(&description, Some(desc) if desc.len() > MAX_DESCRIPTION_LEN) {
+        return Err(ContractError::DescriptionTooLong {
+            max: MAX_DESCRIPTION_LEN,
+        });
+    }
+    let mut state = STATE.load(deps.storage)?;
+    state.description = description;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new())
 }
 
-/// Returns Result<(), ContractError>
+/// Returns Result<Response, ContractError>
 ///
-/// makes sure that the contract state is active
+/// sets the block height from which the offspring can no longer be incremented or reset. Can only
+/// be executed by owner.
 ///
 /// # Arguments
 ///
-/// * `state` - a reference to the State of the contract.
-fn enforce_active(storage: &dyn Storage) -> Result<(), ContractError> {
-    if IS_ACTIVE.load(storage)? {
-        Ok(())
-    } else {
-        Err(ContractError::Inactive {})
+/// * `deps`   - DepsMut containing all the contract's external dependencies
+/// * `info`   - Carries the info of who sent the message and how much native funds were sent along
+/// * `height` - block height the offspring expires at. Removes the expiry if omitted
+pub fn try_set_expiry(
+    deps: DepsMut,
+    info: MessageInfo,
+    height: Option<u64>,
+) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    EXPIRY.save(deps.storage, &height)?;
+
+    Ok(Response::new())
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// appends text to the description on a new line. Can only be executed by owner. The combined
+/// description is returned in the response data.
+///
+/// # Arguments
+///
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+/// * `text` - text appended to the description
+pub fn try_append_description(
+    deps: DepsMut,
+    info: MessageInfo,
+    text: String,
+) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut state = STATE.load(deps.storage)?;
+    let description = match state.description {
+        Some(mut description) => {
+            description.push('\n');
+            description.push_str(&text);
+            description
+        }
+        None => text,
+    };
+    if description.len() > MAX_DESCRIPTION_LEN {
+        return Err(ContractError::DescriptionTooLong {
+            max: MAX_DESCRIPTION_LEN,
+        });
+    }
+    state.description = Some(description.clone());
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new().set_data(to_binary(&HandleAnswer::AppendDescription { description })?))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// asks the factory whether it registered this offspring and stores the answer. Can be executed by
+/// anyone, since the answer only comes from the factory.
+///
+/// # Arguments
+///
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `env`  - Env of contract's environment
+pub fn try_confirm_registered(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let factory = FACTORY_INFO.load(deps.storage)?;
+    let offspring_info_msg = FactoryQueryMsg::OffspringInfo {
+        offspring: env.contract.address,
+    };
+    let response: OffspringInfoWrapper =
+        offspring_info_msg.query(deps.querier, factory.code_hash, factory.address.to_string())?;
+    let is_registered = response.offspring_info.is_registered;
+    IS_REGISTERED.save(deps.storage, &is_registered)?;
+
+    Ok(Response::new().add_attribute("is_registered", is_registered.to_string()))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// increases the counter and reports it to the factory. The counter is increased by 2 if the
+/// offspring has a parity, so that the count keeps it. Can be executed by anyone, unless the
+/// offspring was created with owner_only_increment, or with respect_factory_policy while the
+/// factory disallows public increments.
+///
+/// # Arguments
+///
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `env`  - Env of contract's environment
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+#[cfg(feature = "counter")]
+pub fn try_increment(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    try_increment_by(deps, env, info, 1)
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// increases the counter by steps in one go and reports it to the factory. Anyone who can
+/// Increment can execute it. Like Increment, each step is 2 if the offspring has a parity.
+///
+/// # Arguments
+///
+/// * `deps`  - DepsMut containing all the contract's external dependencies
+/// * `env`   - Env of contract's environment
+/// * `info`  - Carries the info of who sent the message and how much native funds were sent along
+/// * `steps` - number of increments to apply
+#[cfg(feature = "counter")]
+pub fn try_increment_by(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    steps: u32,
+) -> Result<Response, ContractError> {
+    enforce_active(deps.storage, &env)?;
+    enforce_unlocked(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    if info.sender != OWNER.load(deps.storage)?
+        && (state.owner_only_increment
+            || (state.respect_factory_policy && !public_increments_allowed(deps.as_ref())?))
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    // a step of 1 would always break the parity
+    let step = if state.allowed_parity.is_some() { 2 } else { 1 };
+    let count = i32::try_from(steps)
+        .ok()
+        .and_then(|steps| steps.checked_mul(step))
+        .and_then(|amount| state.count.checked_add(amount))
+        .ok_or(ContractError::CountOverflow {})?;
+    enforce_parity(&state, count)?;
+    state.count = count;
+    STATE.save(deps.storage, &state)?;
+    record_count_change(deps.storage, &env, info.sender, state.count)?;
+
+    Ok(Response::new().add_messages(report_count_msgs(deps.storage, &state)?))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// resets the counter to count and reports it to the factory. Can only be executed by owner. The
+/// count before the reset is returned in the response data and logged as `previous_count`.
+///
+/// # Arguments
+///
+/// * `deps`  - DepsMut containing all the contract's external dependencies
+/// * `env`   - Env of contract's environment
+/// * `info`  - Carries the info of who sent the message and how much native funds were sent along
+/// * `count` - The value to reset the counter to.
+#[cfg(feature = "counter")]
+pub fn try_reset(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    count: i32,
+) -> Result<Response, ContractError> {
+    enforce_active(deps.storage, &env)?;
+    enforce_unlocked(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    enforce_parity(&state, count)?;
+    let previous_count = state.count;
+    state.count = count;
+    STATE.save(deps.storage, &state)?;
+    record_count_change(deps.storage, &env, info.sender, count)?;
+
+    Ok(Response::new()
+        .add_messages(report_count_msgs(deps.storage, &state)?)
+        .add_attribute("previous_count", previous_count.to_string())
+        .set_data(to_binary(&HandleAnswer::Reset { previous_count })?))
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// permanently locks the count, so that it can no longer be changed. Can only be executed by
+/// owner. There is no way to unlock it.
+///
+/// # Arguments
+///
+/// * `deps` - DepsMut containing all the contract's external dependencies
+/// * `info` - Carries the info of who sent the message and how much native funds were sent along
+#[cfg(feature = "counter")]
+pub fn try_lock(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    IS_LOCKED.save(deps.storage, &true)?;
+
+    Ok(Response::new())
+}
+
+/// Returns Result<Response, ContractError>
+///
+/// sets the counter to new and reports it to the factory, but only if the count currently is
+/// expected. Can only be executed by owner. The count before the change is returned in the
+/// response data.
+///
+/// # Arguments
+///
+/// * `deps`     - DepsMut containing all the contract's external dependencies
+/// * `env`      - Env of contract's environment
+/// * `info`     - Carries the info of who sent the message and how much native funds were sent along
+/// * `expected` - the count the counter must have for it to be set
+/// * `new`      - The value to set the counter to.
+#[cfg(feature = "counter")]
+pub fn try_compare_and_set(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    expected: i32,
+    new: i32,
+) -> Result<Response, ContractError> {
+    enforce_active(deps.storage, &env)?;
+    enforce_unlocked(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    if state.count != expected {
+        return Err(ContractError::Conflict {
+            expected,
+            actual: state.count,
+        });
+    }
+    enforce_parity(&state, new)?;
+    let previous_count = state.count;
+    state.count = new;
+    STATE.save(deps.storage, &state)?;
+    record_count_change(deps.storage, &env, info.sender, new)?;
+
+    Ok(Response::new()
+        .add_messages(report_count_msgs(deps.storage, &state)?)
+        .set_data(to_binary(&HandleAnswer::CompareAndSet { previous_count })?))
+}
+
+/// Returns Result<(), ContractError>
+///
+/// appends a count change to the history, dropping the oldest change once the history holds
+/// MAX_HISTORY_LEN changes
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `env`     - a reference to the Env of contract's environment
+/// * `actor`   - address that changed the count
+/// * `count`   - the new count
+#[cfg(feature = "counter")]
+fn record_count_change(
+    storage: &mut dyn Storage,
+    env: &Env,
+    actor: Addr,
+    count: i32,
+) -> Result<(), ContractError> {
+    let next = HISTORY_NEXT.may_load(storage)?.unwrap_or(0);
+    if next >= MAX_HISTORY_LEN {
+        COUNT_HISTORY.remove(storage, &(next - MAX_HISTORY_LEN))?;
+    }
+    let change = CountChange {
+        height: env.block.height,
+        actor,
+        count,
+    };
+    COUNT_HISTORY.insert(storage, &next, &change)?;
+    HISTORY_NEXT.save(storage, &(next + 1))?;
+    Ok(())
+}
+
+/// Returns Result<Vec<CosmosMsg>, ContractError>
+///
+/// creates the message that reports the count to the factory, if the offspring was created with
+/// report_count. Otherwise no message is created, so that the count stays private
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `state`   - a reference to the offspring's state holding the current count
+#[cfg(feature = "counter")]
+fn report_count_msgs(
+    storage: &dyn Storage,
+    state: &State,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    if !state.report_count {
+        return Ok(vec![]);
+    }
+    let factory = FACTORY_INFO.load(storage)?;
+    Ok(vec![FactoryExecuteMsg::ReportCount { count: state.count }
+        .to_cosmos_msg(
+            factory.code_hash,
+            factory.address.to_string(),
+            None,
+        )?])
+}
+
+/////////////////////////////////////// Query /////////////////////////////////////
+/// Returns Result<Binary, ContractError>
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+/// * `env`  - Env of contract's environment
+/// * `msg`  - QueryMsg passed in with the query call
+#[entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        #[cfg(feature = "counter")]
+        QueryMsg::GetCount {
+            address,
+            viewing_key,
+            permit,
+        } => Ok(to_binary(&query_count(
+            deps,
+            env,
+            permit,
+            address,
+            viewing_key,
+        )?)?),
+        #[cfg(feature = "counter")]
+        QueryMsg::CountHistory {
+            permit,
+            address,
+            viewing_key,
+            start_page,
+            page_size,
+        } => Ok(to_binary(&query_count_history(
+            deps,
+            env,
+            permit,
+            address,
+            viewing_key,
+            start_page,
+            page_size,
+        )?)?),
+        #[cfg(feature = "counter")]
+        QueryMsg::CountDeltaSince {
+            permit,
+            address,
+            viewing_key,
+            height,
+        } => Ok(to_binary(&query_count_delta_since(
+            deps,
+            env,
+            permit,
+            address,
+            viewing_key,
+            height,
+        )?)?),
+        QueryMsg::FactoryRecord {} => Ok(to_binary(&query_factory_record(deps, env)?)?),
+        QueryMsg::SyncStatus {} => Ok(to_binary(&query_sync_status(deps, env)?)?),
+        QueryMsg::Info {} => Ok(to_binary(&query_info(deps)?)?),
+        QueryMsg::AmIOwner {
+            permit,
+            address,
+            viewing_key,
+        } => Ok(to_binary(&query_am_i_owner(
+            deps,
+            env,
+            permit,
+            address,
+            viewing_key,
+        )?)?),
+        QueryMsg::GetMeta {
+            permit,
+            address,
+            viewing_key,
+            key,
+        } => Ok(to_binary(&query_meta(
+            deps,
+            env,
+            permit,
+            address,
+            viewing_key,
+            key,
+        )?)?),
+    }
+}
+
+/// Returns Result<QueryAnswer, ContractError> displaying this offspring's identity info.
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+fn query_info(deps: Deps) -> Result<QueryAnswer, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    Ok(QueryAnswer::Info {
+        factory: FACTORY_INFO.load(deps.storage)?,
+        owner: OWNER.load(deps.storage)?,
+        label: state.label,
+        title: state.title,
+        is_active: IS_ACTIVE.load(deps.storage)?,
+        is_registered: IS_REGISTERED.may_load(deps.storage)?.unwrap_or(false),
+        #[cfg(feature = "counter")]
+        is_locked: IS_LOCKED.may_load(deps.storage)?.unwrap_or(false),
+        expiry: EXPIRY.may_load(deps.storage)?.flatten(),
+    })
+}
+
+/// Returns Result<QueryAnswer, ContractError> displaying whether the authenticated querier is the
+/// owner.
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `env`         - Env of contract's environment
+/// * `permit`      - optional query permit to authenticate the query request. Takes precedence over the viewing key.
+/// * `address`     - optional address whose viewing key is being validated.
+/// * `viewing_key` - Optional string key used to authenticate the query.
+fn query_am_i_owner(
+    deps: Deps,
+    env: Env,
+    permit: Option<Permit>,
+    address: Option<String>,
+    viewing_key: Option<String>,
+) -> Result<QueryAnswer, ContractError> {
+    let addr = authenticate(deps, env, permit, address, viewing_key)?;
+    Ok(QueryAnswer::AmIOwner {
+        is_owner: OWNER.load(deps.storage)? == addr,
+    })
+}
+
+/// Returns Result<QueryAnswer, ContractError> displaying the metadata value of a key. Can only be
+/// queried by the owner
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `env`         - Env of contract's environment
+/// * `permit`      - optional query permit to authenticate the query request. Takes precedence over the viewing key.
+/// * `address`     - optional address whose viewing key is being validated.
+/// * `viewing_key` - Optional string key used to authenticate the query.
+/// * `key`         - metadata key
+fn query_meta(
+    deps: Deps,
+    env: Env,
+    permit: Option<Permit>,
+    address: Option<String>,
+    viewing_key: Option<String>,
+    key: String,
+) -> Result<QueryAnswer, ContractError> {
+    // a renounced offspring is owned by itself
+    if OWNER.load(deps.storage)? != env.contract.address {
+        enforce_owner(deps, env, permit, address, viewing_key)?;
+    }
+    Ok(QueryAnswer::GetMeta {
+        value: META.get(deps.storage, &key),
+    })
+}
+
+/// Returns Result<QueryAnswer, ContractError> displaying what the factory recorded about this
+/// offspring.
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+/// * `env`  - Env of contract's environment
+fn query_factory_record(deps: Deps, env: Env) -> Result<QueryAnswer, ContractError> {
+    let my_info = query_my_info(deps, env)?;
+    Ok(QueryAnswer::FactoryRecord {
+        label: my_info.info.label,
+        is_active: my_info.is_active,
+    })
+}
+
+/// Returns Result<QueryAnswer, ContractError> comparing this offspring's status with the status
+/// the factory recorded.
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+/// * `env`  - Env of contract's environment
+fn query_sync_status(deps: Deps, env: Env) -> Result<QueryAnswer, ContractError> {
+    let my_info = query_my_info(deps, env)?;
+    Ok(QueryAnswer::SyncStatus {
+        is_active: IS_ACTIVE.load(deps.storage)?,
+        factory_is_active: my_info.is_active,
+    })
+}
+
+/// Returns Result<GetMyInfo, ContractError>, what the factory recorded about this offspring
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+/// * `env`  - Env of contract's environment
+fn query_my_info(deps: Deps, env: Env) -> Result<GetMyInfo, ContractError> {
+    let factory = FACTORY_INFO.load(deps.storage)?;
+    let get_my_info_msg = FactoryQueryMsg::GetMyInfo {
+        offspring: env.contract.address,
+    };
+    let response: GetMyInfoWrapper =
+        get_my_info_msg.query(deps.querier, factory.code_hash, factory.address.to_string())?;
+    Ok(response.get_my_info)
+}
+
+/// Returns Result<bool, ContractError>, whether the factory lets anyone increment the counter of
+/// offspring that respect its policy
+///
+/// # Arguments
+///
+/// * `deps` - Deps containing all the contract's external dependencies
+#[cfg(feature = "counter")]
+fn public_increments_allowed(deps: Deps) -> Result<bool, ContractError> {
+    let factory = FACTORY_INFO.load(deps.storage)?;
+    let response: PublicIncrementsAllowedWrapper = FactoryQueryMsg::PublicIncrementsAllowed {}
+        .query(deps.querier, factory.code_hash, factory.address.to_string())?;
+    Ok(response.public_increments_allowed.is_allowed)
+}
+
+/// Returns Result<QueryAnswer, ContractError> displaying the count. Anyone can query the count of
+/// an offspring whose owner renounced ownership.
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `env`         - Env of contract's environment
+/// * `permit`      - optional query permit to authenticate the query request. Takes precedence over the viewing key.
+/// * `address`     - optional address whose viewing key is being validated.
+/// * `viewing_key` - Optional string key used to authenticate the query.
+#[cfg(feature = "counter")]
+fn query_count(
+    deps: Deps,
+    env: Env,
+    permit: Option<Permit>,
+    address: Option<String>,
+    viewing_key: Option<String>,
+) -> Result<QueryAnswer, ContractError> {
+    // a renounced offspring is owned by itself
+    if OWNER.load(deps.storage)? != env.contract.address {
+        enforce_owner(deps, env, permit, address, viewing_key)?;
+    }
+    let state: State = STATE.load(deps.storage)?;
+    Ok(QueryAnswer::CountResponse { count: state.count })
+}
+
+/// Returns Result<QueryAnswer, ContractError> displaying the latest count changes, oldest first.
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `env`         - Env of contract's environment
+/// * `permit`      - optional query permit to authenticate the query request. Takes precedence over the viewing key.
+/// * `address`     - optional address whose viewing key is being validated.
+/// * `viewing_key` - Optional string key used to authenticate the query.
+/// * `start_page`  - optional start page for the count changes returned and listed
+/// * `page_size`   - optional number of count changes to return in this page
+#[cfg(feature = "counter")]
+fn query_count_history(
+    deps: Deps,
+    env: Env,
+    permit: Option<Permit>,
+    address: Option<String>,
+    viewing_key: Option<String>,
+    start_page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<QueryAnswer, ContractError> {
+    // a renounced offspring is owned by itself
+    if OWNER.load(deps.storage)? != env.contract.address {
+        enforce_owner(deps, env, permit, address, viewing_key)?;
+    }
+    let start_page = start_page.unwrap_or(0) as u64;
+    let size = page_size_or_default(page_size) as u64;
+
+    // the history holds the changes numbered from oldest to next - 1
+    let next = HISTORY_NEXT.may_load(deps.storage)?.unwrap_or(0);
+    let oldest = next.saturating_sub(MAX_HISTORY_LEN);
+    let start = oldest
+        .saturating_add(start_page.saturating_mul(size))
+        .min(next);
+    let end = start.saturating_add(size).min(next);
+    let history = (start..end)
+        .filter_map(|seq| COUNT_HISTORY.get(deps.storage, &seq))
+        .collect();
+
+    Ok(QueryAnswer::CountHistory { history })
+}
+
+/// Returns Result<QueryAnswer, ContractError> displaying the net change of the count after a block
+/// height. Fails if the history no longer holds the count at that height
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `env`         - Env of contract's environment
+/// * `permit`      - optional query permit to authenticate the query request. Takes precedence over the viewing key.
+/// * `address`     - optional address whose viewing key is being validated.
+/// * `viewing_key` - Optional string key used to authenticate the query.
+/// * `height`      - block height after which count changes are summed
+#[cfg(feature = "counter")]
+fn query_count_delta_since(
+    deps: Deps,
+    env: Env,
+    permit: Option<Permit>,
+    address: Option<String>,
+    viewing_key: Option<String>,
+    height: u64,
+) -> Result<QueryAnswer, ContractError> {
+    // a renounced offspring is owned by itself
+    if OWNER.load(deps.storage)? != env.contract.address {
+        enforce_owner(deps, env, permit, address, viewing_key)?;
+    }
+    let count = STATE.load(deps.storage)?.count;
+
+    // the count at the height is the one set by the latest change up to it, or the initial count
+    // if nothing changed it up to then
+    let next = HISTORY_NEXT.may_load(deps.storage)?.unwrap_or(0);
+    let oldest = next.saturating_sub(MAX_HISTORY_LEN);
+    let mut base = None;
+    for seq in (oldest..next).rev() {
+        if let Some(change) = COUNT_HISTORY.get(deps.storage, &seq) {
+            if change.height <= height {
+                base = Some(change.count);
+                break;
+            }
+        }
+    }
+    let base = match base {
+        Some(base) => base,
+        // older changes were dropped, so the count at the height is unknown
+        None if oldest > 0 => return Err(ContractError::HistoryUnavailable { height }),
+        None => INITIAL_COUNT
+            .may_load(deps.storage)?
+            .ok_or(ContractError::HistoryUnavailable { height })?,
+    };
+
+    Ok(QueryAnswer::CountDeltaSince {
+        delta: count as i64 - base as i64,
+    })
+}
+
+/// Returns Result<(), ContractError>
+///
+/// makes sure that the querier is the owner, authenticated with either a permit or a viewing key.
+/// A permit takes precedence, in which case the address and viewing key are ignored.
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `env`         - Env of contract's environment
+/// * `permit`      - optional query permit to authenticate the query request. Takes precedence over the viewing key.
+/// * `address`     - optional address whose viewing key is being validated.
+/// * `viewing_key` - Optional string key used to authenticate the query.
+#[cfg_attr(not(feature = "counter"), allow(dead_code))]
+fn enforce_owner(
+    deps: Deps,
+    env: Env,
+    permit: Option<Permit>,
+    address: Option<String>,
+    viewing_key: Option<String>,
+) -> Result<(), ContractError> {
+    let addr = authenticate(deps, env, permit, address, viewing_key)?;
+
+    if OWNER.load(deps.storage)? == addr {
+        Ok(())
+    } else {
+        Err(ContractError::Unauthorized {})
+    }
+}
+
+/// Returns Result<Addr, ContractError>, the address of the querier
+///
+/// authenticates the querier with either a permit or a viewing key. A permit takes precedence, in
+/// which case the address and viewing key are ignored.
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies
+/// * `env`         - Env of contract's environment
+/// * `permit`      - optional query permit to authenticate the query request. Takes precedence over the viewing key.
+/// * `address`     - optional address whose viewing key is being validated.
+/// * `viewing_key` - Optional string key used to authenticate the query.
+fn authenticate(
+    deps: Deps,
+    env: Env,
+    permit: Option<Permit>,
+    address: Option<String>,
+    viewing_key: Option<String>,
+) -> Result<Addr, ContractError> {
+    if let Some(permit) = permit {
+        enforce_valid_permit(deps, env, permit)
+    } else if let (Some(address), Some(viewing_key)) = (address, viewing_key) {
+        let addr = deps.api.addr_validate(&address)?;
+        enforce_valid_viewing_key(deps, &addr, viewing_key)?;
+        Ok(addr)
+    } else {
+        Err(ContractError::Unauthorized {})
+    }
+}
+
+/// Returns Result<(), ContractError>
+///
+/// makes sure that the address and the viewing key match in the factory contract.
+///
+/// # Arguments
+///
+/// * `deps`        - Deps containing all the contract's external dependencies.
+/// * `state`       - a reference to the State of the contract.
+/// * `address`     - a reference to the address whose viewing key is being validated.
+/// * `viewing_key` - String key used to authenticate a query.
+fn enforce_valid_viewing_key(
+    deps: Deps,
+    address: &Addr,
+    viewing_key: String,
+) -> Result<(), ContractError> {
+    let factory = FACTORY_INFO.load(deps.storage)?;
+    let key_valid_msg = FactoryQueryMsg::IsKeyValid {
+        address: address.clone(),
+        viewing_key,
+    };
+    let key_valid_response: IsKeyValidWrapper =
+        key_valid_msg.query(deps.querier, factory.code_hash, factory.address.to_string())?;
+    // if authenticated
+    if key_valid_response.is_key_valid.is_valid {
+        Ok(())
+    } else {
+        Err(ContractError::ViewingKeyOrUnauthorized {})
+    }
+}
+
+/// Returns Result<Addr, ContractError>, the address of the permit's signer
+///
+/// validates the permit locally if the offspring was created with local permit validation,
+/// otherwise asks the factory.
+///
+/// # Arguments
+///
+/// * `deps`   - Deps containing all the contract's external dependencies
+/// * `env`    - Env of contract's environment
+/// * `permit` - permit offered for authentication
+fn enforce_valid_permit(deps: Deps, env: Env, permit: Permit) -> Result<Addr, ContractError> {
+    if STATE.load(deps.storage)?.local_permit_validation {
+        let address = validate(
+            deps,
+            PREFIX_REVOKED_PERMITS,
+            &permit,
+            env.contract.address.to_string(),
+            Some("secret"),
+        )?;
+        return Ok(deps.api.addr_validate(&address)?);
+    }
+    let factory = FACTORY_INFO.load(deps.storage)?;
+    let permit_valid_msg = FactoryQueryMsg::IsPermitValid { permit };
+    let permit_valid_resp: IsPermitValidWrapper =
+        permit_valid_msg.query(deps.querier, factory.code_hash, factory.address.to_string())?;
+    if permit_valid_resp.is_key_valid.is_valid {
+        permit_valid_resp
+            .is_key_valid
+            .address
+            .ok_or(ContractError::Unauthorized {})
+    } else {
+        Err(ContractError::Unauthorized {})
+    }
+}
+
+/// Returns Result<(), ContractError>
+///
+/// makes sure that the contract state is active and has not expired
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `env`     - a reference to the Env of contract's environment
+#[cfg_attr(not(feature = "counter"), allow(dead_code))]
+fn enforce_active(storage: &dyn Storage, env: &Env) -> Result<(), ContractError> {
+    if !IS_ACTIVE.load(storage)? {
+        return Err(ContractError::Inactive {});
+    }
+    match EXPIRY.may_load(storage)?.flatten() {
+        Some(height) if env.block.height >= height => Err(ContractError::Expired { height }),
+        _ => Ok(()),
+    }
+}
+
+/// Returns Result<(), ContractError>
+///
+/// makes sure that the factory's code hash is a 64 character hex string, so that callbacks to the
+/// factory can succeed
+///
+/// # Arguments
+///
+/// * `factory` - a reference to the factory's code hash and address
+fn validate_factory(factory: &ContractInfo) -> Result<(), ContractError> {
+    if factory.code_hash.len() != 64 || !factory.code_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::InvalidFactory {
+            msg: "code_hash must be a 64 character hex string".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Returns Result<(), ContractError>
+///
+/// makes sure that the count is not locked
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+#[cfg(feature = "counter")]
+fn enforce_unlocked(storage: &dyn Storage) -> Result<(), ContractError> {
+    if IS_LOCKED.may_load(storage)?.unwrap_or(false) {
+        return Err(ContractError::Locked {});
+    }
+    Ok(())
+}
+
+/// Returns Result<(), ContractError>
+///
+/// makes sure the title is not too long
+///
+/// # Arguments
+///
+/// * `title` - a reference to the optional title being checked
+fn enforce_title_len(title: &Option<String>) -> Result<(), ContractError> {
+    if matches!(title, Some(title) if title.len() > MAX_TITLE_LEN) {
+        return Err(ContractError::TitleTooLong { max: MAX_TITLE_LEN });
+    }
+    Ok(())
+}
+
+/// Returns Result<(), ContractError>
+///
+/// makes sure the count has the parity the offspring was created with, if any
+///
+/// # Arguments
+///
+/// * `state` - a reference to the offspring's state
+/// * `count` - the count being checked
+#[cfg(feature = "counter")]
+fn enforce_parity(state: &State, count: i32) -> Result<(), ContractError> {
+    if matches!(state.allowed_parity, Some(parity) if !parity.allows(count)) {
+        return Err(ContractError::InvalidCount {});
+    }
+    Ok(())
+}
+
+/// Returns u32
+///
+/// resolves an optional page size, treating both None and zero as DEFAULT_PAGE_SIZE so that a
+/// page size of zero does not silently list nothing. Larger page sizes are capped at MAX_PAGE_SIZE
+///
+/// # Arguments
+///
+/// * `page_size` - optional page size given in a query
+#[cfg(feature = "counter")]
+fn page_size_or_default(page_size: Option<u32>) -> u32 {
+    match page_size {
+        None | Some(0) => DEFAULT_PAGE_SIZE,
+        Some(size) => size.min(MAX_PAGE_SIZE),
+    }
+}
+
+#[cfg(all(test, feature = "counter"))]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+        MOCK_CONTRACT_ADDR,
+    };
+    use cosmwasm_std::{coins, from_binary, ContractResult, OwnedDeps, SystemResult, WasmQuery};
+    use serde::Deserialize;
+
+    use crate::factory_msg::{IsKeyValid, IsPermitValid};
+    use crate::msg::Parity;
+
+    const FACTORY_HASH: &str = "d519793af2623773f46967192c9afcd9f2e3a2ba0fd927ea6bf3448a723bde6b";
+
+    fn init_msg() -> InstantiateMsg {
+        InstantiateMsg {
+            factory: ContractInfo {
+                code_hash: FACTORY_HASH.to_string(),
+                address: Addr::unchecked("factory"),
+            },
+            label: "counter".to_string(),
+            title: None,
+            description: None,
+            owner: Addr::unchecked("owner"),
+            count: 0,
+            owner_only_increment: false,
+            allowed_parity: None,
+            local_permit_validation: false,
+            owner_can_set_factory: false,
+            respect_factory_policy: false,
+            report_count: false,
+            start_inactive: false,
+            correlation_id: None,
+        }
+    }
+
+    fn setup(msg: InstantiateMsg) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("factory", &[]), msg).unwrap();
+        deps
+    }
+
+    /// the factory queries the mocked factory answers
+    #[derive(Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum MockFactoryQuery {
+        IsKeyValid {
+            address: String,
+            viewing_key: String,
+        },
+        IsPermitValid {},
+    }
+
+    /// makes the mocked factory accept the given (address, viewing key) pairs
+    fn set_factory_keys(querier: &mut MockQuerier, keys: &[(&str, &str)]) {
+        set_factory_auth(querier, keys, None)
+    }
+
+    /// makes the mocked factory accept the given (address, viewing key) pairs, and any permit as
+    /// signed by permit_signer if given
+    fn set_factory_auth(
+        querier: &mut MockQuerier,
+        keys: &[(&str, &str)],
+        permit_signer: Option<&str>,
+    ) {
+        let keys: Vec<(String, String)> = keys
+            .iter()
+            .map(|(address, key)| (address.to_string(), key.to_string()))
+            .collect();
+        let permit_signer = permit_signer.map(Addr::unchecked);
+        querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { msg, .. } => {
+                let answer = match from_binary(msg).unwrap() {
+                    MockFactoryQuery::IsKeyValid {
+                        address,
+                        viewing_key,
+                    } => to_binary(&IsKeyValidWrapper {
+                        is_key_valid: IsKeyValid {
+                            is_valid: keys.contains(&(address.clone(), viewing_key)),
+                            address: Some(Addr::unchecked(address)),
+                        },
+                    }),
+                    MockFactoryQuery::IsPermitValid { .. } => to_binary(&IsPermitValidWrapper {
+                        is_key_valid: IsPermitValid {
+                            is_valid: permit_signer.is_some(),
+                            address: permit_signer.clone(),
+                        },
+                    }),
+                };
+                SystemResult::Ok(ContractResult::Ok(answer.unwrap()))
+            }
+            _ => panic!("unexpected query"),
+        });
+    }
+
+    #[test]
+    fn permit_takes_precedence_over_viewing_key() {
+        let mut deps = setup(init_msg());
+        let permit: Permit = from_binary(&Binary::from(
+            br#"{"params":{"allowed_tokens":["cosmos2contract"],"permit_name":"test","chain_id":"secret-4","permissions":["owner"]},"signature":{"pub_key":{"type":"tendermint/PubKeySecp256k1","value":"AAAA"},"signature":"AAAA"}}"#
+                .as_slice(),
+        ))
+        .unwrap();
+        let get_count = |address: &str, key: &str| QueryMsg::GetCount {
+            permit: Some(permit.clone()),
+            address: Some(address.to_string()),
+            viewing_key: Some(key.to_string()),
+        };
+
+        // the owner's permit is accepted along with an invalid viewing key
+        set_factory_auth(&mut deps.querier, &[], Some("owner"));
+        query(deps.as_ref(), mock_env(), get_count("mallory", "bad_key")).unwrap();
+
+        // another signer's permit is rejected along with the owner's valid viewing key
+        set_factory_auth(&mut deps.querier, &[("owner", "key")], Some("mallory"));
+        let err = query(deps.as_ref(), mock_env(), get_count("owner", "key"));
+        assert!(matches!(err, Err(ContractError::Unauthorized {})));
+    }
+
+    #[test]
+    fn empty_factory_code_hash_is_rejected() {
+        let mut deps = mock_dependencies();
+        let mut msg = init_msg();
+        msg.factory.code_hash = String::new();
+        let err = instantiate(deps.as_mut(), mock_env(), mock_info("factory", &[]), msg);
+        assert!(matches!(err, Err(ContractError::InvalidFactory { .. })));
+    }
+
+    #[test]
+    fn zero_page_size_lists_default_page() {
+        let mut deps = setup(init_msg());
+        set_factory_keys(&mut deps.querier, &[("owner", "key")]);
+        for _ in 0..(DEFAULT_PAGE_SIZE + 5) {
+            let msg = ExecuteMsg::Increment {};
+            execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
+        }
+
+        let msg = QueryMsg::CountHistory {
+            permit: None,
+            address: Some("owner".to_string()),
+            viewing_key: Some("key".to_string()),
+            start_page: None,
+            page_size: Some(0),
+        };
+        let answer: QueryAnswer =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        match answer {
+            QueryAnswer::CountHistory { history } => {
+                assert_eq!(history.len(), DEFAULT_PAGE_SIZE as usize)
+            }
+            _ => panic!("unexpected answer"),
+        }
+    }
+
+    #[test]
+    fn transfer_moves_query_access() {
+        let mut deps = setup(init_msg());
+        set_factory_keys(
+            &mut deps.querier,
+            &[("owner", "old_key"), ("new_owner", "new_key")],
+        );
+        let msg = ExecuteMsg::TransferOwnership {
+            new_owner: "new_owner".to_string(),
+            new_owner_key_hint: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let get_count = |address: &str, key: &str| QueryMsg::GetCount {
+            permit: None,
+            address: Some(address.to_string()),
+            viewing_key: Some(key.to_string()),
+        };
+        let err = query(deps.as_ref(), mock_env(), get_count("owner", "old_key"));
+        assert!(matches!(err, Err(ContractError::Unauthorized {})));
+        query(deps.as_ref(), mock_env(), get_count("new_owner", "new_key")).unwrap();
+    }
+
+    #[test]
+    fn renounced_offspring_has_no_owner() {
+        let mut deps = setup(init_msg());
+        let msg = ExecuteMsg::RenounceOwnership {};
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        for msg in [
+            ExecuteMsg::Reset { count: 5 },
+            ExecuteMsg::Deactivate { force: false },
+            ExecuteMsg::SetMeta {
+                key: "key".to_string(),
+                value: "value".to_string(),
+            },
+            ExecuteMsg::RenounceOwnership {},
+        ] {
+            let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+            assert!(matches!(err, Err(ContractError::Unauthorized {})));
+        }
+
+        // the owner-only queries are open to anyone
+        let msg = QueryMsg::CountHistory {
+            permit: None,
+            address: None,
+            viewing_key: None,
+            start_page: None,
+            page_size: None,
+        };
+        query(deps.as_ref(), mock_env(), msg).unwrap();
+        let msg = QueryMsg::GetMeta {
+            permit: None,
+            address: None,
+            viewing_key: None,
+            key: "key".to_string(),
+        };
+        query(deps.as_ref(), mock_env(), msg).unwrap();
+        let msg = QueryMsg::CountDeltaSince {
+            permit: None,
+            address: None,
+            viewing_key: None,
+            height: 0,
+        };
+        let answer: QueryAnswer =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert!(matches!(answer, QueryAnswer::CountDeltaSince { delta: 0 }));
+    }
+
+    #[test]
+    fn renounce_fails_while_holding_funds() {
+        let mut deps = setup(init_msg());
+        deps.querier
+            .update_balance(MOCK_CONTRACT_ADDR, coins(100, "uscrt"));
+
+        let msg = ExecuteMsg::RenounceOwnership {};
+        let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert!(matches!(err, Err(ContractError::RenounceFundsRemaining {})));
+        assert_eq!(OWNER.load(&deps.storage).unwrap(), Addr::unchecked("owner"));
+    }
+
+    #[test]
+    fn owner_revokes_local_permits() {
+        let mut deps = setup(InstantiateMsg {
+            local_permit_validation: true,
+            ..init_msg()
+        });
+        let msg = ExecuteMsg::RevokePermit {
+            permit_name: "permit".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mallory", &[]),
+            msg.clone(),
+        );
+        assert!(matches!(err, Err(ContractError::Unauthorized {})));
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        assert!(RevokedPermits::is_permit_revoked(
+            &deps.storage,
+            PREFIX_REVOKED_PERMITS,
+            "owner",
+            "permit"
+        ));
+    }
+
+    #[test]
+    fn pending_offspring_starts_inactive() {
+        let mut deps = setup(InstantiateMsg {
+            start_inactive: true,
+            ..init_msg()
+        });
+        assert!(!IS_ACTIVE.load(&deps.storage).unwrap());
+
+        let msg = ExecuteMsg::FactoryUnfreeze {};
+        execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), msg).unwrap();
+        assert!(IS_ACTIVE.load(&deps.storage).unwrap());
+    }
+
+    #[test]
+    fn increments_keep_parity() {
+        let mut deps = setup(InstantiateMsg {
+            count: 1,
+            allowed_parity: Some(Parity::Odd),
+            ..init_msg()
+        });
+        let msg = ExecuteMsg::Increment {};
+        execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
+        assert_eq!(STATE.load(&deps.storage).unwrap().count, 3);
+
+        let msg = ExecuteMsg::IncrementBy { steps: 3 };
+        execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
+        assert_eq!(STATE.load(&deps.storage).unwrap().count, 9);
+    }
+
+    #[test]
+    fn page_sizes_are_capped() {
+        assert_eq!(page_size_or_default(None), DEFAULT_PAGE_SIZE);
+        assert_eq!(page_size_or_default(Some(0)), DEFAULT_PAGE_SIZE);
+        assert_eq!(page_size_or_default(Some(MAX_PAGE_SIZE)), MAX_PAGE_SIZE);
+        assert_eq!(page_size_or_default(Some(MAX_PAGE_SIZE + 1)), MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn description_length_boundary() {
+        let mut deps = setup(init_msg());
+        let set_description = |len| ExecuteMsg::SetDescription {
+            description: Some("d".repeat(len)),
+        };
+
+        let msg = set_description(MAX_DESCRIPTION_LEN);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mallory", &[]),
+            msg.clone(),
+        );
+        assert!(matches!(err, Err(ContractError::Unauthorized {})));
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        let msg = set_description(MAX_DESCRIPTION_LEN + 1);
+        let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert!(matches!(err, Err(ContractError::DescriptionTooLong { .. })));
+        assert_eq!(
+            STATE
+                .load(&deps.storage)
+                .unwrap()
+                .description
+                .unwrap()
+                .len(),
+            MAX_DESCRIPTION_LEN
+        );
     }
 }
@@ -9,9 +9,57 @@ pub enum ContractError {
     #[error("This contract is inactive")]
     Inactive {},
 
+    #[error("This contract expired at height {height}")]
+    Expired { height: u64 },
+
+    #[error("The count is locked")]
+    Locked {},
+
+    #[error("This contract still holds funds, withdraw them first or force the deactivation")]
+    FundsRemaining {},
+
+    #[error("This contract still holds funds, withdraw them before renouncing ownership")]
+    RenounceFundsRemaining {},
+
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Count is not allowed by this offspring's parity")]
+    InvalidCount {},
+
+    #[error("Count would overflow")]
+    CountOverflow {},
+
+    #[error("Count is {actual}, expected {expected}")]
+    Conflict { expected: i32, actual: i32 },
+
+    #[error("Label can be at most {max} bytes long")]
+    LabelTooLong { max: usize },
+
+    #[error("Description can be at most {max} bytes long")]
+    DescriptionTooLong { max: usize },
+
+    #[error("Title can be at most {max} bytes long")]
+    TitleTooLong { max: usize },
+
+    #[error("Key hint can be at most {max} bytes long")]
+    KeyHintTooLong { max: usize },
+
+    #[error("Metadata key can be at most {max} bytes long")]
+    MetaKeyTooLong { max: usize },
+
+    #[error("Metadata value can be at most {max} bytes long")]
+    MetaValueTooLong { max: usize },
+
+    #[error("At most {max} metadata keys can be set")]
+    TooManyMetaKeys { max: u32 },
+
+    #[error("Invalid factory: {msg}")]
+    InvalidFactory { msg: String },
+
+    #[error("The count history does not reach back to height {height}")]
+    HistoryUnavailable { height: u64 },
+
     #[error("This address is unauthorized and/or viewing key is not valid")]
     ViewingKeyOrUnauthorized {},
 
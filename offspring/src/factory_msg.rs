@@ -6,6 +6,7 @@ use secret_toolkit::{
     utils::{HandleCallback, Query},
 };
 
+use crate::msg::ContractInfo;
 use crate::state::BLOCK_SIZE;
 
 /// Factory handle messages to be used by offspring.
@@ -14,9 +15,22 @@ use crate::state::BLOCK_SIZE;
 pub enum FactoryExecuteMsg {
     /// DeactivateOffspring tells the factory that the offspring is inactive.
     DeactivateOffspring {
-        /// offspring's owner
-        owner: Addr,
+        /// offspring's count when it was deactivated. Omitted if the offspring does not keep a count
+        #[serde(skip_serializing_if = "Option::is_none")]
+        final_count: Option<i32>,
     },
+    /// ReportCount tells the factory the offspring's current count.
+    #[cfg(feature = "counter")]
+    ReportCount { count: i32 },
+    /// TransferOffspring tells the factory the offspring has a new owner.
+    TransferOffspring {
+        /// offspring's new owner
+        new_owner: Addr,
+    },
+    /// RenounceOffspring tells the factory the offspring's owner renounced ownership.
+    RenounceOffspring {},
+    /// PurgeOffspring tells the factory to remove the inactive offspring from its records.
+    PurgeOffspring {},
 }
 
 impl HandleCallback for FactoryExecuteMsg {
@@ -30,9 +44,21 @@ impl HandleCallback for FactoryExecuteMsg {
 pub struct FactoryOffspringInfo {
     /// label used when initializing offspring
     pub label: String,
+    /// display name the offspring was initialized with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     pub owner: Addr,
     pub address: Addr,
     pub code_hash: String,
+    /// the count the offspring was initialized with
+    #[cfg(feature = "counter")]
+    pub count: i32,
+    /// whether the offspring reports its count to the factory
+    #[cfg(feature = "counter")]
+    pub report_count: bool,
+    /// correlation id the factory instantiated this offspring with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
 }
 
 /// the factory's query messages this offspring will call
@@ -48,6 +74,20 @@ pub enum FactoryQueryMsg {
     },
     /// authenticates the supplied permit. This should be called by offspring.
     IsPermitValid { permit: Permit },
+    /// displays what the factory recorded about this offspring
+    GetMyInfo {
+        /// this offspring's address
+        offspring: Addr,
+    },
+    /// displays what the factory recorded about this offspring, if it is registered
+    OffspringInfo {
+        /// this offspring's address
+        offspring: Addr,
+    },
+    /// displays whether the factory lets anyone increment the counter of offspring that respect
+    /// its policy
+    #[cfg(feature = "counter")]
+    PublicIncrementsAllowed {},
 }
 
 impl Query for FactoryQueryMsg {
@@ -58,6 +98,8 @@ impl Query for FactoryQueryMsg {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct IsKeyValid {
     pub is_valid: bool,
+    #[serde(default)]
+    pub address: Option<Addr>,
 }
 
 /// IsKeyValid wrapper struct
@@ -78,3 +120,52 @@ pub struct IsPermitValid {
 pub struct IsPermitValidWrapper {
     pub is_key_valid: IsPermitValid,
 }
+
+/// this corresponds to StoreOffspringInfo in factory
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FactoryOffspringRecord {
+    /// offspring address and code hash
+    pub contract: ContractInfo,
+    /// label used when initializing offspring
+    pub label: String,
+}
+
+/// offspring info recorded by the factory
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetMyInfo {
+    pub info: FactoryOffspringRecord,
+    pub is_active: bool,
+}
+
+/// GetMyInfo wrapper struct
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetMyInfoWrapper {
+    pub get_my_info: GetMyInfo,
+}
+
+/// whether the factory registered the offspring
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OffspringInfo {
+    pub is_registered: bool,
+    pub is_active: bool,
+}
+
+/// OffspringInfo wrapper struct
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OffspringInfoWrapper {
+    pub offspring_info: OffspringInfo,
+}
+
+/// the factory's public increments policy
+#[cfg(feature = "counter")]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PublicIncrementsAllowed {
+    pub is_allowed: bool,
+}
+
+/// PublicIncrementsAllowed wrapper struct
+#[cfg(feature = "counter")]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PublicIncrementsAllowedWrapper {
+    pub public_increments_allowed: PublicIncrementsAllowed,
+}
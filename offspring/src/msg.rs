@@ -3,42 +3,261 @@ use schemars::JsonSchema;
 use secret_toolkit::permit::Permit;
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "counter")]
+use crate::state::CountChange;
+
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 pub struct InstantiateMsg {
     /// factory contract code hash and address
     pub factory: ContractInfo,
     /// label used when initializing offspring
     pub label: String,
+    /// optional display name of this offspring. Unlike the label, the owner can change it
+    #[serde(default)]
+    pub title: Option<String>,
     /// Optional text description of this offspring
     pub description: Option<String>,
 
     pub owner: Addr,
+    #[cfg(feature = "counter")]
     pub count: i32,
+    /// whether only the owner can increment the counter. Default: false
+    #[cfg(feature = "counter")]
+    #[serde(default)]
+    pub owner_only_increment: bool,
+    /// optional parity every count must have. Increments then step by 2 so that the count keeps
+    /// it. Any count is allowed if omitted
+    #[cfg(feature = "counter")]
+    #[serde(default)]
+    pub allowed_parity: Option<Parity>,
+    /// whether query permits are validated by this offspring instead of the factory. Permits must
+    /// then be signed for this offspring's address, and are revoked with this offspring's
+    /// RevokePermit instead of the factory's. Default: false
+    #[serde(default)]
+    pub local_permit_validation: bool,
+    /// whether the owner, and not only the factory, can point this offspring to a new factory.
+    /// Default: false
+    #[serde(default)]
+    pub owner_can_set_factory: bool,
+    /// whether only the owner can increment the counter while the factory disallows public
+    /// increments. Default: false
+    #[cfg(feature = "counter")]
+    #[serde(default)]
+    pub respect_factory_policy: bool,
+    /// whether the count is reported to the factory, which shows it to anyone in its count
+    /// rankings. Default: false
+    #[cfg(feature = "counter")]
+    #[serde(default)]
+    pub report_count: bool,
+    /// whether this offspring starts inactive, because the factory waits for the owner to
+    /// activate it. The factory reactivates it with FactoryUnfreeze. Default: false
+    #[serde(default)]
+    pub start_inactive: bool,
+    /// optional id the factory uses to tie this instantiation to its CreateOffspring request
+    #[serde(default)]
+    pub correlation_id: Option<String>,
 }
 
 /// Handle messages
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
+    // Increment increases the count by 1, or by 2 if the offspring has a parity
+    #[cfg(feature = "counter")]
     Increment {},
-    Reset { count: i32 },
-    // Deactivate can only be called by owner in this template
-    Deactivate {},
+    // IncrementBy increments the count steps times in one go. Same permissions as Increment
+    #[cfg(feature = "counter")]
+    IncrementBy {
+        steps: u32,
+    },
+    #[cfg(feature = "counter")]
+    Reset {
+        count: i32,
+    },
+    // Clear resets the count to zero. Same as Reset { count: 0 }
+    #[cfg(feature = "counter")]
+    Clear {},
+    // Lock permanently prevents any further change of the count. Can only be called by owner
+    #[cfg(feature = "counter")]
+    Lock {},
+    // CompareAndSet sets the count to new only if it currently is expected. Can only be called by
+    // owner
+    #[cfg(feature = "counter")]
+    CompareAndSet {
+        expected: i32,
+        new: i32,
+    },
+    // Deactivate can only be called by owner in this template. It fails while this contract holds
+    // funds unless force is true, so that they are not stranded
+    Deactivate {
+        #[serde(default)]
+        force: bool,
+    },
+    // Withdraw sends all funds this contract holds to the given address. Can only be called by owner
+    Withdraw {
+        to: String,
+    },
+    // TransferOwnership makes new_owner the owner of this offspring and moves it to the new owner's
+    // lists in the factory. The new owner authenticates with their own factory viewing key, which
+    // new_owner_key_hint can remind them to set. Can only be called by owner
+    TransferOwnership {
+        new_owner: String,
+        new_owner_key_hint: Option<String>,
+    },
+    // RenounceOwnership permanently gives up ownership of this offspring. Afterwards no one can
+    // call the owner-only messages, such as Reset and Deactivate, and the owner-only queries, such
+    // as GetCount, can be queried by anyone. This can not be undone. It fails while this contract
+    // holds funds, since no one could withdraw them afterwards. Can only be called by owner
+    RenounceOwnership {},
+    // SetMeta sets the metadata value of a key. At most MAX_META_KEYS (20) keys can be set. Can
+    // only be called by owner
+    SetMeta {
+        key: String,
+        value: String,
+    },
+    // RemoveMeta removes the metadata value of a key. Can only be called by owner
+    RemoveMeta {
+        key: String,
+    },
+    // RevokePermit revokes the owner's query permits with the given name when this offspring
+    // validates permits itself, since it does not see permits revoked in the factory. Can only be
+    // called by owner
+    RevokePermit {
+        permit_name: String,
+    },
+    // Retire asks the factory to remove this offspring from its records. Can only be called by
+    // owner once the offspring is inactive
+    Retire {},
+    // FactoryFreeze deactivates this offspring. Can only be called by the factory
+    FactoryFreeze {},
+    // FactoryUnfreeze reactivates this offspring. Can only be called by the factory
+    FactoryUnfreeze {},
+    // SetFactory points this offspring to a new factory. Can only be called by the current factory,
+    // or by the owner if the offspring was created with owner_can_set_factory
+    SetFactory {
+        factory: ContractInfo,
+    },
+    // SetTitle changes the display name of this offspring. Can only be called by owner
+    SetTitle {
+        title: Option<String>,
+    },
+    // SetDescription replaces the description. Removes it if omitted. Can only be called by owner
+    SetDescription {
+        description: Option<String>,
+    },
+    // SetExpiry sets the block height from which this offspring can no longer be incremented or
+    // reset. Removes the expiry if height is omitted. Can only be called by owner
+    SetExpiry {
+        height: Option<u64>,
+    },
+    // AppendDescription appends text to the description on a new line. Can only be called by owner
+    AppendDescription {
+        text: String,
+    },
+    // ConfirmRegistered asks the factory whether it registered this offspring and stores the
+    // answer. Can be called by anyone
+    ConfirmRegistered {},
+}
+
+/// Responses from handle functions
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HandleAnswer {
+    /// response to Reset and Clear
+    #[cfg(feature = "counter")]
+    Reset {
+        /// the count before it was reset
+        previous_count: i32,
+    },
+    /// response to CompareAndSet
+    #[cfg(feature = "counter")]
+    CompareAndSet {
+        /// the count before it was set
+        previous_count: i32,
+    },
+    /// response to AppendDescription
+    AppendDescription {
+        /// the description after the text was appended
+        description: String,
+    },
 }
 
-/// Queries
+/// Queries. Authenticated queries prefer a permit over an address/viewing key pair when both are
+/// provided. Note that the factory's queries prefer the address/viewing key pair instead
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     // GetCount returns the current count as a json-encoded number. Can only be queried by the owner,
     // to demonstrate how to use the viewing key in the factory.
+    #[cfg(feature = "counter")]
     GetCount {
-        /// permit to authenticate. Disregarded if viewing key - address pair is provided.
+        /// permit to authenticate. If provided, the querier's address is derived from the permit
+        /// alone and address/viewing_key are ignored.
+        permit: Option<Permit>,
+        /// address to authenticate as a viewer. Used with viewing_key instead of a permit
+        address: Option<String>,
+        /// viewer's viewing key. Used with address instead of a permit
+        viewing_key: Option<String>,
+    },
+    // CountHistory returns the latest count changes, oldest first. Can only be queried by the owner
+    #[cfg(feature = "counter")]
+    CountHistory {
+        /// permit to authenticate. If provided, the querier's address is derived from the permit
+        /// alone and address/viewing_key are ignored.
+        permit: Option<Permit>,
+        /// address to authenticate as a viewer. Used with viewing_key instead of a permit
+        address: Option<String>,
+        /// viewer's viewing key. Used with address instead of a permit
+        viewing_key: Option<String>,
+        /// start page for the count changes returned and listed. Default: 0
+        #[serde(default)]
+        start_page: Option<u32>,
+        /// optional number of count changes to return in this page. Default: DEFAULT_PAGE_SIZE,
+        /// at most MAX_PAGE_SIZE
+        #[serde(default)]
+        page_size: Option<u32>,
+    },
+    // CountDeltaSince returns the net change of the count after a block height. Can only be
+    // queried by the owner
+    #[cfg(feature = "counter")]
+    CountDeltaSince {
+        /// permit to authenticate. If provided, the querier's address is derived from the permit
+        /// alone and address/viewing_key are ignored.
+        permit: Option<Permit>,
+        /// address to authenticate as a viewer. Used with viewing_key instead of a permit
+        address: Option<String>,
+        /// viewer's viewing key. Used with address instead of a permit
+        viewing_key: Option<String>,
+        /// block height after which count changes are summed
+        height: u64,
+    },
+    // FactoryRecord returns what the factory recorded about this offspring
+    FactoryRecord {},
+    // SyncStatus compares this offspring's status with the status the factory recorded
+    SyncStatus {},
+    // Info returns this offspring's non-sensitive identity info. Does not require authentication
+    Info {},
+    // AmIOwner returns whether the authenticated querier is the owner
+    AmIOwner {
+        /// permit to authenticate. If provided, the querier's address is derived from the permit
+        /// alone and address/viewing_key are ignored.
+        permit: Option<Permit>,
+        /// address to authenticate. Used with viewing_key instead of a permit
+        address: Option<String>,
+        /// viewing key of the address. Used with address instead of a permit
+        viewing_key: Option<String>,
+    },
+    // GetMeta returns the metadata value of a key. Can only be queried by the owner
+    GetMeta {
+        /// permit to authenticate. If provided, the querier's address is derived from the permit
+        /// alone and address/viewing_key are ignored.
         permit: Option<Permit>,
-        /// address to authenticate as a viewer
+        /// address to authenticate as a viewer. Used with viewing_key instead of a permit
         address: Option<String>,
-        /// viewer's viewing key
+        /// viewer's viewing key. Used with address instead of a permit
         viewing_key: Option<String>,
+        /// metadata key
+        key: String,
     },
 }
 
@@ -51,9 +270,82 @@ pub struct ContractInfo {
     pub address: Addr,
 }
 
+/// parity a count can be restricted to
+#[cfg(feature = "counter")]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+#[cfg(feature = "counter")]
+impl Parity {
+    /// returns true if the count has this parity
+    pub fn allows(&self, count: i32) -> bool {
+        match self {
+            Parity::Even => count % 2 == 0,
+            Parity::Odd => count % 2 != 0,
+        }
+    }
+}
+
 /// responses to queries
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryAnswer {
+    #[cfg(feature = "counter")]
     CountResponse { count: i32 },
+    #[cfg(feature = "counter")]
+    CountHistory {
+        /// count changes, oldest first
+        history: Vec<CountChange>,
+    },
+    #[cfg(feature = "counter")]
+    CountDeltaSince {
+        /// net change of the count after the queried height
+        delta: i64,
+    },
+    FactoryRecord {
+        /// label the factory recorded
+        label: String,
+        /// whether the factory lists this offspring as active
+        is_active: bool,
+    },
+    SyncStatus {
+        /// whether this offspring is active
+        is_active: bool,
+        /// whether the factory lists this offspring as active
+        factory_is_active: bool,
+    },
+    Info {
+        /// factory contract code hash and address
+        factory: ContractInfo,
+        /// owner of this offspring
+        owner: Addr,
+        /// label used when initializing this offspring
+        label: String,
+        /// display name of this offspring
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// whether this offspring is active
+        is_active: bool,
+        /// whether the factory confirmed that this offspring is registered
+        is_registered: bool,
+        /// whether the count is permanently locked
+        #[cfg(feature = "counter")]
+        is_locked: bool,
+        /// block height from which this offspring is treated as inactive
+        #[serde(skip_serializing_if = "Option::is_none")]
+        expiry: Option<u64>,
+    },
+    AmIOwner {
+        /// whether the authenticated querier is the owner
+        is_owner: bool,
+    },
+    GetMeta {
+        /// the key's metadata value. Omitted if the key is not set
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<String>,
+    },
 }
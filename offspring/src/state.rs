@@ -1,14 +1,41 @@
 use schemars::JsonSchema;
-use secret_toolkit::storage::Item;
+use secret_toolkit::storage::{Item, Keymap};
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::Addr;
 
 use crate::msg::ContractInfo;
+#[cfg(feature = "counter")]
+use crate::msg::Parity;
 
 /// pad handle responses and log attributes to blocks of 256 bytes to prevent leaking info based on
 /// response size
 pub const BLOCK_SIZE: usize = 256;
+/// the maximum length of the label in bytes
+pub const MAX_LABEL_LEN: usize = 128;
+/// the maximum length of the title in bytes
+pub const MAX_TITLE_LEN: usize = 128;
+/// the maximum length of the description in bytes
+pub const MAX_DESCRIPTION_LEN: usize = 1024;
+/// the maximum length of the key hint given to TransferOwnership in bytes
+pub const MAX_KEY_HINT_LEN: usize = 64;
+/// the maximum number of metadata entries
+pub const MAX_META_KEYS: u32 = 20;
+/// the maximum length of a metadata key in bytes
+pub const MAX_META_KEY_LEN: usize = 64;
+/// the maximum length of a metadata value in bytes
+pub const MAX_META_VALUE_LEN: usize = 256;
+/// the maximum number of count changes kept in the history. Older changes are dropped
+#[cfg(feature = "counter")]
+pub const MAX_HISTORY_LEN: u64 = 100;
+/// the default number of count changes listed during queries
+#[cfg(feature = "counter")]
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+/// the maximum number of count changes listed in one page
+#[cfg(feature = "counter")]
+pub const MAX_PAGE_SIZE: u32 = 100;
+/// prefix of the revoked permits storage used when permits are validated locally
+pub const PREFIX_REVOKED_PERMITS: &str = "revoked_permits";
 
 /// stores factory code hash and address
 pub const FACTORY_INFO: Item<ContractInfo> = Item::new(b"factory_info");
@@ -16,17 +43,66 @@ pub const FACTORY_INFO: Item<ContractInfo> = Item::new(b"factory_info");
 pub const OWNER: Item<Addr> = Item::new(b"owner");
 /// stores whether or not the contract is still active
 pub const IS_ACTIVE: Item<bool> = Item::new(b"active");
+/// stores whether the factory confirmed that this offspring is registered
+pub const IS_REGISTERED: Item<bool> = Item::new(b"registered");
+/// optional block height from which the contract is treated as inactive
+pub const EXPIRY: Item<Option<u64>> = Item::new(b"expiry");
 /// used to store the state of this template contract
 pub const STATE: Item<State> = Item::new(b"state");
+/// storage of the app-specific metadata the owner attached to this offspring
+pub static META: Keymap<String, String> = Keymap::new(b"meta");
+/// storage of the latest count changes keyed by their sequence number
+#[cfg(feature = "counter")]
+pub static COUNT_HISTORY: Keymap<u64, CountChange> = Keymap::new(b"count_history");
+/// sequence number of the next count change
+#[cfg(feature = "counter")]
+pub const HISTORY_NEXT: Item<u64> = Item::new(b"history_next");
+/// the count the offspring was initialized with
+#[cfg(feature = "counter")]
+pub const INITIAL_COUNT: Item<i32> = Item::new(b"initial_count");
+/// stores whether the count is permanently locked
+#[cfg(feature = "counter")]
+pub const IS_LOCKED: Item<bool> = Item::new(b"locked");
 
-/// State of the offspring contract
-#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+/// State of the offspring contract. Without the `counter` feature only the identity fields remain
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
 pub struct State {
     /// label used when initializing offspring
     pub label: String,
+    /// optional display name of this offspring. Unlike the label, the owner can change it
+    pub title: Option<String>,
     /// Optional text description of this offspring
     pub description: Option<String>,
 
     /// the count for the counter
+    #[cfg(feature = "counter")]
+    pub count: i32,
+    /// whether only the owner can increment the counter
+    #[cfg(feature = "counter")]
+    pub owner_only_increment: bool,
+    /// optional parity every count must have
+    #[cfg(feature = "counter")]
+    pub allowed_parity: Option<Parity>,
+    /// whether query permits are validated locally instead of by the factory
+    pub local_permit_validation: bool,
+    /// whether the owner can point this offspring to a new factory
+    pub owner_can_set_factory: bool,
+    /// whether the factory's public increments policy applies to this offspring
+    #[cfg(feature = "counter")]
+    pub respect_factory_policy: bool,
+    /// whether the count is reported to the factory, which shows it publicly
+    #[cfg(feature = "counter")]
+    pub report_count: bool,
+}
+
+/// a change of the count
+#[cfg(feature = "counter")]
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct CountChange {
+    /// block height the count changed at
+    pub height: u64,
+    /// address that changed the count
+    pub actor: Addr,
+    /// the new count
     pub count: i32,
 }